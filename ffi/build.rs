@@ -0,0 +1,26 @@
+//! generates the C header divedeco.h from the exported items of src/lib.rs
+//! into OUT_DIR. the tests compile the C example against it and check the
+//! committed include/divedeco.h is up to date
+
+use std::env;
+
+fn main() {
+  let crate_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is not set");
+  println!("cargo:rerun-if-changed=src/lib.rs");
+  println!("cargo:rerun-if-changed=cbindgen.toml");
+
+  let out_dir = env::var("OUT_DIR").expect("OUT_DIR is not set");
+
+  // the integration test compiles the C example for the same target
+  // against the generated header
+  println!("cargo:rustc-env=DIVEDECO_TARGET={}", env::var("TARGET").expect("TARGET is not set"));
+  println!("cargo:rustc-env=DIVEDECO_INCLUDE={}", out_dir);
+
+  let config = cbindgen::Config::from_root_or_default(&crate_dir);
+  cbindgen::Builder::new()
+    .with_crate(&crate_dir)
+    .with_config(config)
+    .generate()
+    .expect("Error generating the C header")
+    .write_to_file(format!("{}/divedeco.h", out_dir));
+}
@@ -0,0 +1,46 @@
+/* looks up a dive plan with the divedeco C bindings */
+
+#include <stdio.h>
+
+#include "divedeco.h"
+
+static int check(const char *lookup, DdStatus status) {
+  if (status != DD_STATUS_OK) {
+    fprintf(stderr, "%s: %s\n", lookup, dd_status_message(status));
+    return 1;
+  }
+  return 0;
+}
+
+int main(void) {
+  DdDive dive = { .depth = 60, .bottom_time = 40, .exposure = DD_EXPOSURE_NONE };
+  DdDivePlan plan = { .dive = dive, .surface_interval_time = 90, .next_dive_depth = 50 };
+  DdDive deco_dive = { .depth = 60, .bottom_time = 80, .exposure = DD_EXPOSURE_NONE };
+  DdDecoSchedule schedule;
+  uint16_t ndl, rnt;
+  char group, repet;
+
+  if (check("ndl", dd_no_decompression_limit(dive, &ndl))
+    || check("group", dd_group_letter(dive, &group))
+    || check("repet", dd_repet_letter(plan, &repet))
+    || check("rnt", dd_residual_nitrogen_time(plan, &rnt))
+    || check("deco", dd_deco_schedule(deco_dive, &schedule))) {
+    return 1;
+  }
+
+  printf("ndl %u\n", (unsigned) ndl);
+  printf("group %c\n", group);
+  printf("repet %c\n", repet);
+  printf("rnt %u\n", (unsigned) rnt);
+  printf("deco %u min, repetitive group %c\n", (unsigned) schedule.bottom_time, schedule.repet_group);
+  for (uint8_t stop = 0; stop < schedule.stop_count; stop++) {
+    printf("stop %u fsw %u min\n", (unsigned) schedule.stops[stop].depth, (unsigned) schedule.stops[stop].time);
+  }
+  for (uint8_t stop = 0; stop < schedule.o2_stop_count; stop++) {
+    printf("o2 stop %u fsw %u min\n", (unsigned) schedule.o2_stops[stop].depth, (unsigned) schedule.o2_stops[stop].time);
+  }
+
+  printf("%s\n", dd_status_message(dd_deco_schedule(dive, &schedule)));
+  printf("%s\n", dd_status_message(dd_no_decompression_limit(dive, NULL)));
+  return 0;
+}
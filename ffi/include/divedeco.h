@@ -0,0 +1,168 @@
+/* diving-decompression C bindings, generated by cbindgen from src/lib.rs: do not edit */
+
+#ifndef DIVEDECO_H
+#define DIVEDECO_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// largest number of stops of an air decompression schedule
+#define DD_MAX_STOPS 16
+
+// conditions of a dive that require the next longer bottom time schedule
+enum DdExposure
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+  // the dive is neither unusually cold nor strenuous
+  DD_EXPOSURE_NONE = 0,
+  // the diver was unusually cold during the dive
+  DD_EXPOSURE_COLD = 1,
+  // the workload during the dive was strenuous
+  DD_EXPOSURE_STRENUOUS = 2,
+  // the dive was unusually cold and the workload strenuous
+  DD_EXPOSURE_COLD_AND_STRENUOUS = 3,
+};
+#ifndef __cplusplus
+typedef uint8_t DdExposure;
+#endif // __cplusplus
+
+// result code of every function of the library
+typedef enum DdStatus {
+  // the lookup succeeded
+  DD_STATUS_OK = 0,
+  // an out pointer is null
+  DD_STATUS_NULL_POINTER = 1,
+  // a depth or time is zero or the exposure is unknown
+  DD_STATUS_INVALID_ARGUMENT = 2,
+  // the dive is outside the tables
+  DD_STATUS_OUTSIDE_TABLES = 3,
+  // the bottom time exceeds the no decompression limit, the dive needs
+  // an air decompression schedule
+  DD_STATUS_DECOMPRESSION_REQUIRED = 4,
+  // the bottom time is within the no decompression limit, the dive has
+  // no air decompression schedule
+  DD_STATUS_NO_DECOMPRESSION_REQUIRED = 5,
+} DdStatus;
+
+// a single dive
+typedef struct DdDive {
+  // depth of the dive expressed in feet of sea water
+  uint16_t depth;
+  // bottom time of the dive expressed in minutes
+  uint16_t bottom_time;
+  // conditions of the dive that require the next longer bottom time
+  // schedule, one of DdExposure
+  uint8_t exposure;
+} DdDive;
+
+// a dive followed by a surface interval and a repetitive dive
+typedef struct DdDivePlan {
+  // the first dive of the plan
+  struct DdDive dive;
+  // the surface interval expressed in minutes
+  uint16_t surface_interval_time;
+  // depth of the next dive expressed in feet of sea water
+  uint16_t next_dive_depth;
+} DdDivePlan;
+
+// a stop of an air decompression schedule
+typedef struct DdDecoStop {
+  // depth of the stop expressed in feet of sea water
+  uint16_t depth;
+  // time of the stop expressed in minutes
+  uint16_t time;
+} DdDecoStop;
+
+// an air decompression schedule
+typedef struct DdDecoSchedule {
+  // bottom time of the schedule row expressed in minutes
+  uint16_t bottom_time;
+  // time from leaving the bottom to the first stop expressed in seconds
+  uint32_t time_to_first_stop;
+  // total ascent time of the in-water air decompression expressed in seconds
+  uint32_t air_total_ascent_time;
+  // total ascent time of the in-water oxygen decompression expressed in seconds
+  uint32_t o2_total_ascent_time;
+  // number of chamber periods of the SurDO2 decompression
+  float o2_chamber_periods;
+  // repetitive group letter after the schedule, 0 when no repetitive
+  // dive is permitted
+  char repet_group;
+  // the schedule is an exceptional exposure
+  bool exceptional_exposure;
+  // SurDO2 is recommended
+  bool surdo2_recommended;
+  // SurDO2 is required
+  bool surdo2_required;
+  // in-water air decompression is not permitted
+  bool strict_surdo2;
+  // number of valid entries in stops
+  uint8_t stop_count;
+  // the air decompression stops, deepest first
+  struct DdDecoStop stops[DD_MAX_STOPS];
+  // number of valid entries in o2_stops
+  uint8_t o2_stop_count;
+  // the stops of the in-water oxygen decompression, deepest first. oxygen
+  // is breathed at 30 fsw and shallower, deeper stops are made on air
+  struct DdDecoStop o2_stops[DD_MAX_STOPS];
+} DdDecoSchedule;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// dd_no_decompression_limit() writes the no decompression limit at the
+// depth of the dive expressed in minutes
+//
+// # Safety
+//
+// ndl must be null or point to a writable uint16_t
+enum DdStatus dd_no_decompression_limit(struct DdDive dive, uint16_t *ndl);
+
+// dd_group_letter() writes the repetitive group letter at the end of the dive
+//
+// # Safety
+//
+// letter must be null or point to a writable char
+enum DdStatus dd_group_letter(struct DdDive dive, char *letter);
+
+// dd_repet_letter() writes the repetitive group letter at the end of the
+// surface interval of the plan
+//
+// # Safety
+//
+// letter must be null or point to a writable char
+enum DdStatus dd_repet_letter(struct DdDivePlan plan, char *letter);
+
+// dd_residual_nitrogen_time() writes the residual nitrogen time of the
+// next dive of the plan expressed in minutes
+//
+// # Safety
+//
+// rnt must be null or point to a writable uint16_t
+enum DdStatus dd_residual_nitrogen_time(struct DdDivePlan plan, uint16_t *rnt);
+
+// dd_deco_schedule() writes the air decompression schedule of a dive
+// whose bottom time exceeds the no decompression limit
+//
+// # Safety
+//
+// schedule must be null or point to a writable DdDecoSchedule
+enum DdStatus dd_deco_schedule(struct DdDive dive, struct DdDecoSchedule *schedule);
+
+// dd_status_message() returns a static, null terminated description of
+// a status code. the code is taken as an int so that any value coming
+// from C is accepted, values that are not a DdStatus return
+// "unknown status"
+const char *dd_status_message(int status);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* DIVEDECO_H */
@@ -0,0 +1,330 @@
+// diving-decompression-ffi crate
+// C bindings of the diving-decompression crate
+
+//! # C bindings of the diving-decompression library
+//!
+//! the NDL, group letter, repetitive group letter, residual nitrogen time
+//! and air decompression schedule lookups are exposed through a stable C
+//! ABI. every function takes plain structs by value, writes its result
+//! through an out pointer and returns a DdStatus code. the build script
+//! generates the header divedeco.h from this file into OUT_DIR, the tests
+//! compile the C example against it and check the committed copy
+//! include/divedeco.h is the same
+
+// Code conventions
+#![deny(non_snake_case)]
+#![deny(non_upper_case_globals)]
+#![deny(unused_imports)]
+#![deny(unused_mut)]
+#![deny(missing_docs)]
+#![deny(dead_code)]
+
+use std::os::raw::{c_char, c_int};
+
+use diving_decompression::{Dive, DivePlan, ExposureModifier};
+use diving_decompression::advisories::AdvisoryCode;
+use diving_decompression::airtables::DecoStops;
+
+/// largest number of stops of an air decompression schedule
+pub const DD_MAX_STOPS: usize = 16;
+
+/// result code of every function of the library
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DdStatus {
+  /// the lookup succeeded
+  Ok = 0,
+  /// an out pointer is null
+  NullPointer = 1,
+  /// a depth or time is zero or the exposure is unknown
+  InvalidArgument = 2,
+  /// the dive is outside the tables
+  OutsideTables = 3,
+  /// the bottom time exceeds the no decompression limit, the dive needs
+  /// an air decompression schedule
+  DecompressionRequired = 4,
+  /// the bottom time is within the no decompression limit, the dive has
+  /// no air decompression schedule
+  NoDecompressionRequired = 5,
+}
+
+/// conditions of a dive that require the next longer bottom time schedule
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DdExposure {
+  /// the dive is neither unusually cold nor strenuous
+  None = 0,
+  /// the diver was unusually cold during the dive
+  Cold = 1,
+  /// the workload during the dive was strenuous
+  Strenuous = 2,
+  /// the dive was unusually cold and the workload strenuous
+  ColdAndStrenuous = 3,
+}
+
+/// a single dive
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct DdDive {
+  /// depth of the dive expressed in feet of sea water
+  pub depth: u16,
+  /// bottom time of the dive expressed in minutes
+  pub bottom_time: u16,
+  /// conditions of the dive that require the next longer bottom time
+  /// schedule, one of DdExposure
+  pub exposure: u8,
+}
+
+/// a dive followed by a surface interval and a repetitive dive
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct DdDivePlan {
+  /// the first dive of the plan
+  pub dive: DdDive,
+  /// the surface interval expressed in minutes
+  pub surface_interval_time: u16,
+  /// depth of the next dive expressed in feet of sea water
+  pub next_dive_depth: u16,
+}
+
+/// a stop of an air decompression schedule
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct DdDecoStop {
+  /// depth of the stop expressed in feet of sea water
+  pub depth: u16,
+  /// time of the stop expressed in minutes
+  pub time: u16,
+}
+
+/// an air decompression schedule
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct DdDecoSchedule {
+  /// bottom time of the schedule row expressed in minutes
+  pub bottom_time: u16,
+  /// time from leaving the bottom to the first stop expressed in seconds
+  pub time_to_first_stop: u32,
+  /// total ascent time of the in-water air decompression expressed in seconds
+  pub air_total_ascent_time: u32,
+  /// total ascent time of the in-water oxygen decompression expressed in seconds
+  pub o2_total_ascent_time: u32,
+  /// number of chamber periods of the SurDO2 decompression
+  pub o2_chamber_periods: f32,
+  /// repetitive group letter after the schedule, 0 when no repetitive
+  /// dive is permitted
+  pub repet_group: c_char,
+  /// the schedule is an exceptional exposure
+  pub exceptional_exposure: bool,
+  /// SurDO2 is recommended
+  pub surdo2_recommended: bool,
+  /// SurDO2 is required
+  pub surdo2_required: bool,
+  /// in-water air decompression is not permitted
+  pub strict_surdo2: bool,
+  /// number of valid entries in stops
+  pub stop_count: u8,
+  /// the air decompression stops, deepest first
+  pub stops: [DdDecoStop; DD_MAX_STOPS],
+  /// number of valid entries in o2_stops
+  pub o2_stop_count: u8,
+  /// the stops of the in-water oxygen decompression, deepest first. oxygen
+  /// is breathed at 30 fsw and shallower, deeper stops are made on air
+  pub o2_stops: [DdDecoStop; DD_MAX_STOPS],
+}
+
+impl DdDive {
+  fn dive(self) -> Result<Dive, DdStatus> {
+    let exposure = match self.exposure {
+      exposure if exposure == DdExposure::None as u8 => ExposureModifier::None,
+      exposure if exposure == DdExposure::Cold as u8 => ExposureModifier::Cold,
+      exposure if exposure == DdExposure::Strenuous as u8 => ExposureModifier::Strenuous,
+      exposure if exposure == DdExposure::ColdAndStrenuous as u8 => ExposureModifier::ColdAndStrenuous,
+      _ => return Err(DdStatus::InvalidArgument),
+    };
+    if self.depth == 0 || self.bottom_time == 0 {
+      return Err(DdStatus::InvalidArgument);
+    }
+    Ok(Dive::new(self.depth, self.bottom_time).with_exposure(exposure))
+  }
+}
+
+impl DdDivePlan {
+  fn plan(self) -> Result<DivePlan, DdStatus> {
+    if self.next_dive_depth == 0 {
+      return Err(DdStatus::InvalidArgument);
+    }
+    Ok(DivePlan::from_dive(self.dive.dive()?, self.surface_interval_time, self.next_dive_depth))
+  }
+}
+
+/// dd_no_decompression_limit() writes the no decompression limit at the
+/// depth of the dive expressed in minutes
+///
+/// # Safety
+///
+/// ndl must be null or point to a writable uint16_t
+#[no_mangle]
+pub unsafe extern "C" fn dd_no_decompression_limit(dive: DdDive, ndl: *mut u16) -> DdStatus {
+  let result = dive.dive().and_then(|dive| {
+    let limit = dive.advised_no_decompression_limit();
+    if limit.has(AdvisoryCode::OutsideNoDecompressionTable) {
+      return Err(DdStatus::OutsideTables);
+    }
+    Ok(limit.value)
+  });
+  write(ndl, result)
+}
+
+/// dd_group_letter() writes the repetitive group letter at the end of the dive
+///
+/// # Safety
+///
+/// letter must be null or point to a writable char
+#[no_mangle]
+pub unsafe extern "C" fn dd_group_letter(dive: DdDive, letter: *mut c_char) -> DdStatus {
+  let result = dive.dive().and_then(|dive| {
+    let group = dive.advised_group_letter();
+    if group.has(AdvisoryCode::OutsideNoDecompressionTable) {
+      return Err(DdStatus::OutsideTables);
+    }
+    if group.has(AdvisoryCode::NoDecompressionLimitExceeded) {
+      return Err(DdStatus::DecompressionRequired);
+    }
+    letter_of(&group.value).ok_or(DdStatus::OutsideTables)
+  });
+  write(letter, result)
+}
+
+/// dd_repet_letter() writes the repetitive group letter at the end of the
+/// surface interval of the plan
+///
+/// # Safety
+///
+/// letter must be null or point to a writable char
+#[no_mangle]
+pub unsafe extern "C" fn dd_repet_letter(plan: DdDivePlan, letter: *mut c_char) -> DdStatus {
+  let result = plan.plan().and_then(|plan| letter_of(&plan.repet_letter()).ok_or(DdStatus::OutsideTables));
+  write(letter, result)
+}
+
+/// dd_residual_nitrogen_time() writes the residual nitrogen time of the
+/// next dive of the plan expressed in minutes
+///
+/// # Safety
+///
+/// rnt must be null or point to a writable uint16_t
+#[no_mangle]
+pub unsafe extern "C" fn dd_residual_nitrogen_time(plan: DdDivePlan, rnt: *mut u16) -> DdStatus {
+  let result = plan.plan().and_then(|plan| {
+    let residual = plan.advised_residual_nitrogen_time();
+    if residual.has(AdvisoryCode::OutsideRepetitiveTables) {
+      return Err(DdStatus::OutsideTables);
+    }
+    Ok(residual.value)
+  });
+  write(rnt, result)
+}
+
+/// dd_deco_schedule() writes the air decompression schedule of a dive
+/// whose bottom time exceeds the no decompression limit
+///
+/// # Safety
+///
+/// schedule must be null or point to a writable DdDecoSchedule
+#[no_mangle]
+pub unsafe extern "C" fn dd_deco_schedule(dive: DdDive, schedule: *mut DdDecoSchedule) -> DdStatus {
+  let result = dive.dive().and_then(|dive| {
+    let row = dive.advised_deco_dive();
+    if row.has(AdvisoryCode::NoDecompressionRequired) {
+      return Err(DdStatus::NoDecompressionRequired);
+    }
+    if row.has(AdvisoryCode::OutsideDecompressionTable) {
+      return Err(DdStatus::OutsideTables);
+    }
+    let row = row.value;
+
+    let (stop_count, stops) = deco_stops(&row.air_deco_stops);
+    let (o2_stop_count, o2_stops) = deco_stops(&row.o2_deco_stops);
+
+    Ok(DdDecoSchedule {
+      bottom_time: row.max_time,
+      time_to_first_stop: seconds(&row.ttfs),
+      air_total_ascent_time: seconds(&row.air_tat),
+      o2_total_ascent_time: seconds(&row.o2_tat),
+      o2_chamber_periods: row.o2cp,
+      repet_group: letter_of(&row.repetgroup_letter).unwrap_or(0),
+      exceptional_exposure: row.exceptional_exposure,
+      surdo2_recommended: row.surdo2_recommended,
+      surdo2_required: row.surdo2_required,
+      strict_surdo2: row.strict_surdo2,
+      stop_count,
+      stops,
+      o2_stop_count,
+      o2_stops,
+    })
+  });
+  write(schedule, result)
+}
+
+/// dd_status_message() returns a static, null terminated description of
+/// a status code. the code is taken as an int so that any value coming
+/// from C is accepted, values that are not a DdStatus return
+/// "unknown status"
+#[no_mangle]
+pub extern "C" fn dd_status_message(status: c_int) -> *const c_char {
+  let message: &'static [u8] = match status {
+    status if status == DdStatus::Ok as c_int => b"ok\0",
+    status if status == DdStatus::NullPointer as c_int => b"an out pointer is null\0",
+    status if status == DdStatus::InvalidArgument as c_int => b"a depth or time is zero or the exposure is unknown\0",
+    status if status == DdStatus::OutsideTables as c_int => b"the dive is outside the tables\0",
+    status if status == DdStatus::DecompressionRequired as c_int => b"the bottom time exceeds the no decompression limit\0",
+    status if status == DdStatus::NoDecompressionRequired as c_int => b"the bottom time is within the no decompression limit\0",
+    _ => b"unknown status\0",
+  };
+  message.as_ptr() as *const c_char
+}
+
+/// writes the result through the out pointer and returns its status
+unsafe fn write<T>(out: *mut T, result: Result<T, DdStatus>) -> DdStatus {
+  if out.is_null() {
+    return DdStatus::NullPointer;
+  }
+  match result {
+    Ok(value) => {
+      out.write(value);
+      DdStatus::Ok
+    }
+    Err(status) => status,
+  }
+}
+
+/// returns the number of stops and the stops of a schedule, deepest first
+fn deco_stops(schedule_stops: &[DecoStops]) -> (u8, [DdDecoStop; DD_MAX_STOPS]) {
+  let mut sorted = schedule_stops.to_vec();
+  sorted.sort_by_key(|stop| std::cmp::Reverse(stop.depth));
+  let mut stops = [DdDecoStop::default(); DD_MAX_STOPS];
+  for (stop, schedule_stop) in stops.iter_mut().zip(sorted.iter()) {
+    *stop = DdDecoStop { depth: schedule_stop.depth, time: schedule_stop.time };
+  }
+  (sorted.len().min(DD_MAX_STOPS) as u8, stops)
+}
+
+/// returns the group letter as a C char, none when it is not a single letter
+fn letter_of(letter: &str) -> Option<c_char> {
+  match letter.as_bytes() {
+    [byte] if byte.is_ascii_uppercase() => Some(*byte as c_char),
+    _ => None,
+  }
+}
+
+/// returns the seconds of a time written as M:SS in the tables, 0 when
+/// the table gives no time
+fn seconds(time: &str) -> u32 {
+  match time.split_once(':') {
+    Some((minutes, seconds)) => {
+      minutes.parse::<u32>().unwrap_or(0) * 60 + seconds.parse::<u32>().unwrap_or(0)
+    }
+    None => time.parse::<u32>().unwrap_or(0) * 60,
+  }
+}
@@ -0,0 +1,87 @@
+use std::env;
+use std::ffi::CStr;
+use std::fs;
+use std::mem::MaybeUninit;
+use std::path::PathBuf;
+use std::process::Command;
+
+use divedeco_ffi::{dd_deco_schedule, dd_status_message, DdDecoSchedule, DdDive, DdExposure, DdStatus};
+
+/// the deps directory holding the test executable and the static library
+fn artifacts() -> PathBuf {
+  let mut dir = env::current_exe().expect("failed to locate the test executable");
+  dir.pop();
+  dir
+}
+
+#[test]
+fn committed_header() {
+  let generated = PathBuf::from(env!("DIVEDECO_INCLUDE")).join("divedeco.h");
+  let committed = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("include").join("divedeco.h");
+  assert!(
+    fs::read_to_string(&committed).ok() == fs::read_to_string(&generated).ok(),
+    "include/divedeco.h is out of date, copy {} over it",
+    generated.display(),
+  );
+}
+
+#[test]
+fn c_example() {
+  let manifest = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+  let artifacts = artifacts();
+  let executable = artifacts.join("divedeco-lookup");
+  let target = env!("DIVEDECO_TARGET");
+
+  let compiler = cc::Build::new()
+    .cargo_metadata(false)
+    .target(target)
+    .host(target)
+    .opt_level(0)
+    .get_compiler();
+  let mut command = compiler.to_command();
+  command
+    .arg("-std=c99")
+    .arg("-I").arg(env!("DIVEDECO_INCLUDE"))
+    .arg(manifest.join("examples").join("lookup.c"))
+    .arg(artifacts.join("libdivedeco_ffi.a"))
+    .arg("-o").arg(&executable);
+  if cfg!(target_os = "linux") {
+    command.args(["-lpthread", "-ldl", "-lm"]);
+  }
+  let status = command.status().expect("failed to run the C compiler");
+  assert!(status.success(), "failed to compile the C example");
+
+  let output = Command::new(&executable).output().expect("failed to run the C example");
+  assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+  assert_eq!(String::from_utf8_lossy(&output.stdout), "ndl 63
+group H
+repet G
+rnt 49
+deco 80 min, repetitive group N
+stop 20 fsw 14 min
+o2 stop 20 fsw 7 min
+the bottom time is within the no decompression limit
+an out pointer is null
+");
+}
+
+#[test]
+fn deco_schedule_below_the_no_decompression_table() {
+  let dive = DdDive { depth: 195, bottom_time: 10, exposure: DdExposure::None as u8 };
+  let mut schedule = MaybeUninit::<DdDecoSchedule>::uninit();
+  assert_eq!(unsafe { dd_deco_schedule(dive, schedule.as_mut_ptr()) }, DdStatus::Ok);
+  let schedule = unsafe { schedule.assume_init() };
+  assert_eq!((schedule.bottom_time, schedule.stop_count), (10, 2));
+  assert_eq!((schedule.stops[0].depth, schedule.stops[0].time), (30, 3));
+  assert_eq!(schedule.o2_stop_count, 2);
+  assert_eq!((schedule.o2_stops[0].depth, schedule.o2_stops[0].time), (30, 2));
+  assert_eq!((schedule.o2_stops[1].depth, schedule.o2_stops[1].time), (20, 4));
+}
+
+#[test]
+fn status_messages() {
+  let message = |status| unsafe { CStr::from_ptr(dd_status_message(status)) }.to_str().unwrap();
+  assert_eq!(message(DdStatus::OutsideTables as i32), "the dive is outside the tables");
+  assert_eq!(message(42), "unknown status");
+  assert_eq!(message(-1), "unknown status");
+}
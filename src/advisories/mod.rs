@@ -0,0 +1,229 @@
+use crate::airtables::RowDeco;
+
+/// deepest depth allowed for scuba air dives expressed in feet of sea water
+pub const SCUBA_DEPTH_LIMIT: u16 = 130;
+
+/// how much attention an advisory demands from the dive supervisor
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+  /// informative note, no action is required
+  Info,
+  /// the dive can be carried out but requires special consideration
+  Caution,
+  /// the dive must not be carried out as planned without further action
+  Warning,
+}
+
+/// unique code of every advisory raised by the calculations
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AdvisoryCode {
+  /// the schedule is an exceptional exposure
+  ExceptionalExposure,
+  /// SurDO2 is recommended due to the extent of the decompression profile
+  SurDO2Recommended,
+  /// SurDO2 is required due to the extent of the decompression profile
+  SurDO2Required,
+  /// in-water air decompression is not permitted for the schedule
+  InWaterAirNotPermitted,
+  /// the depth exceeds the scuba depth limit
+  DepthExceedsScubaLimit,
+  /// the bottom time exceeds the no decompression limit
+  NoDecompressionLimitExceeded,
+  /// the depth exceeds the no-decompression table
+  OutsideNoDecompressionTable,
+  /// the dive is not covered by the air decompression table
+  OutsideDecompressionTable,
+  /// the bottom time is within the no decompression limit, the dive has
+  /// no air decompression schedule
+  NoDecompressionRequired,
+  /// the dive plan is not covered by the repetitive dive tables
+  OutsideRepetitiveTables,
+  /// no repetitive group is assigned after the schedule
+  RepetitiveDiveNotPermitted,
+  /// the next longer bottom time schedule was used for a cold or
+  /// strenuous dive
+  ScheduleAdjusted,
+  /// the accumulated CNS oxygen toxicity exceeds the limit
+  CnsLimitExceeded,
+  /// the accumulated pulmonary oxygen toxicity units exceed the daily limit
+  OtuLimitExceeded,
+  /// oxygen was breathed above the partial pressures of the CNS limits
+  OxygenOutsideCnsLimits,
+  /// the planned gas consumption breaks the cylinder reserve
+  GasReserveBroken,
+}
+
+/// a typed warning relevant to the profile of a calculation
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Advisory {
+  /// how much attention the advisory demands
+  pub severity: Severity,
+  /// unique code of the advisory
+  pub code: AdvisoryCode,
+  /// human readable description of the advisory
+  pub message: String,
+  /// the section of the US Navy dive manual rev7 backing the advisory
+  pub reference: String,
+}
+
+/// the result of a calculation along with the advisories relevant to it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Advised<T> {
+  /// the result of the calculation
+  pub value: T,
+  /// the advisories relevant to the calculation, empty when there is
+  /// nothing to report
+  pub advisories: Vec<Advisory>,
+}
+
+impl Advisory {
+  pub fn new(code: AdvisoryCode) -> Self {
+    //! Instantiates the Advisory object for the given code
+    let (severity, message, reference) = match code {
+      AdvisoryCode::ExceptionalExposure => (
+        Severity::Warning,
+        "exceptional exposure, obtain commanding officer approval",
+        "US Navy Diving Manual rev7, Table 9-9",
+      ),
+      AdvisoryCode::SurDO2Recommended => (
+        Severity::Caution,
+        "SurDO2 is recommended for this schedule",
+        "US Navy Diving Manual rev7, Table 9-9",
+      ),
+      AdvisoryCode::SurDO2Required => (
+        Severity::Warning,
+        "SurDO2 is required for this schedule",
+        "US Navy Diving Manual rev7, Table 9-9",
+      ),
+      AdvisoryCode::InWaterAirNotPermitted => (
+        Severity::Warning,
+        "in-water air decompression not permitted, SurDO2 required",
+        "US Navy Diving Manual rev7, Table 9-9",
+      ),
+      AdvisoryCode::DepthExceedsScubaLimit => (
+        Severity::Caution,
+        "depth exceeds scuba limit, use surface-supplied equipment",
+        "US Navy Diving Manual rev7, chapter 7",
+      ),
+      AdvisoryCode::NoDecompressionLimitExceeded => (
+        Severity::Caution,
+        "bottom time exceeds the no-decompression limit, decompression is required",
+        "US Navy Diving Manual rev7, Table 9-7",
+      ),
+      AdvisoryCode::OutsideNoDecompressionTable => (
+        Severity::Warning,
+        "depth exceeds the no-decompression table, no-decompression diving is not permitted",
+        "US Navy Diving Manual rev7, Table 9-7",
+      ),
+      AdvisoryCode::OutsideDecompressionTable => (
+        Severity::Warning,
+        "the dive is outside the air decompression table",
+        "US Navy Diving Manual rev7, Table 9-9",
+      ),
+      AdvisoryCode::NoDecompressionRequired => (
+        Severity::Info,
+        "bottom time is within the no-decompression limit, no decompression is required",
+        "US Navy Diving Manual rev7, Table 9-7",
+      ),
+      AdvisoryCode::OutsideRepetitiveTables => (
+        Severity::Warning,
+        "the dive plan is outside the repetitive dive tables",
+        "US Navy Diving Manual rev7, Table 9-8",
+      ),
+      AdvisoryCode::RepetitiveDiveNotPermitted => (
+        Severity::Caution,
+        "no repetitive group is assigned after this schedule, repetitive dives are not permitted",
+        "US Navy Diving Manual rev7, Table 9-9",
+      ),
+      AdvisoryCode::ScheduleAdjusted => (
+        Severity::Info,
+        "cold or strenuous dive, the next longer bottom time schedule was used",
+        "US Navy Diving Manual rev7, chapter 9",
+      ),
+      AdvisoryCode::CnsLimitExceeded => (
+        Severity::Warning,
+        "CNS oxygen toxicity exceeds 100%, reduce the oxygen exposure",
+        "NOAA Diving Manual, oxygen exposure limits",
+      ),
+      AdvisoryCode::OtuLimitExceeded => (
+        Severity::Warning,
+        "pulmonary oxygen toxicity exceeds the daily OTU limit, reduce the oxygen exposure",
+        "NOAA Diving Manual, oxygen exposure limits",
+      ),
+      AdvisoryCode::OxygenOutsideCnsLimits => (
+        Severity::Warning,
+        "oxygen was breathed above 1.6 ata and is not accounted in the CNS oxygen toxicity, follow the US Navy oxygen exposure limits",
+        "US Navy Diving Manual rev7, chapter 9",
+      ),
+      AdvisoryCode::GasReserveBroken => (
+        Severity::Warning,
+        "the planned gas consumption breaks the cylinder reserve",
+        "US Navy Diving Manual rev7, chapter 7",
+      ),
+    };
+
+    Self {
+      severity,
+      code,
+      message: String::from(message),
+      reference: String::from(reference),
+    }
+  }
+}
+
+impl<T> Advised<T> {
+  pub fn new(value: T, advisories: Vec<Advisory>) -> Self {
+    //! Instantiates a new Advised object
+    Self {
+      value,
+      advisories,
+    }
+  }
+
+  pub fn has(&self, code: AdvisoryCode) -> bool {
+    //! returns true when an advisory with the given code was raised
+    self.advisories.iter().any(|advisory| advisory.code == code)
+  }
+
+  pub fn max_severity(&self) -> Option<Severity> {
+    //! returns the highest severity among the advisories, if any
+    self.advisories.iter().map(|advisory| advisory.severity).max()
+  }
+}
+
+impl RowDeco {
+  pub fn advisories(&self) -> Vec<Advisory> {
+    //! returns the advisories flagged by the decompression schedule
+    let mut advisories = vec![];
+
+    if self.exceptional_exposure {
+      advisories.push(Advisory::new(AdvisoryCode::ExceptionalExposure));
+    }
+    if self.strict_surdo2 {
+      advisories.push(Advisory::new(AdvisoryCode::InWaterAirNotPermitted));
+    } else if self.surdo2_required {
+      advisories.push(Advisory::new(AdvisoryCode::SurDO2Required));
+    } else if self.surdo2_recommended {
+      advisories.push(Advisory::new(AdvisoryCode::SurDO2Recommended));
+    }
+    if self.repetgroup_letter == "N/A" {
+      advisories.push(Advisory::new(AdvisoryCode::RepetitiveDiveNotPermitted));
+    }
+
+    advisories
+  }
+}
+
+/// depth_advisories() returns the advisories relevant to the depth of a dive
+/// expressed in feet of sea water
+pub fn depth_advisories(depth: u16) -> Vec<Advisory> {
+  let mut advisories = vec![];
+
+  if depth > SCUBA_DEPTH_LIMIT {
+    advisories.push(Advisory::new(AdvisoryCode::DepthExceedsScubaLimit));
+  }
+
+  advisories
+}
@@ -108,7 +108,7 @@ pub struct TableRnt {
 }
 
 /// an item from a row of the table for air decompression
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct DecoStops {
   /// the nominal depth of a specific decompression stop
   /// expressed in feet of sea water
@@ -181,34 +181,126 @@ pub struct TableAirDeco {
   pub table_data: Vec::<DecoDepth>,
 }
 
+/// breathing gas of a step of a recompression treatment table
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TreatmentGas {
+  /// 100% oxygen on the built-in breathing system
+  Oxygen,
+  /// chamber air
+  Air,
+  /// chamber air or a 50/50 nitrogen-oxygen treatment gas
+  AirOrNitrox50,
+  /// chamber air, or oxygen periods when the patient tolerates them
+  AirOrOxygen,
+}
+
+/// a step of a recompression treatment table
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
+pub struct TreatmentStep {
+  /// depth at the beginning of the step expressed in feet of sea water
+  pub start_depth: u16,
+  /// depth at the end of the step expressed in feet of sea water. it
+  /// differs from the start_depth for travel steps
+  pub end_depth: u16,
+  /// duration of the step expressed in minutes
+  pub time: u16,
+  /// breathing gas of the patient during the step
+  pub gas: TreatmentGas,
+}
+
+/// an extension allowed by a recompression treatment table
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TreatmentExtension {
+  /// depth of the extension expressed in feet of sea water
+  pub depth: u16,
+  /// maximum number of extensions allowed at the depth
+  pub max_count: u8,
+  /// index of the table step after which the extensions are inserted
+  pub after_step: usize,
+  /// the steps of a single extension
+  pub steps: Vec<TreatmentStep>,
+}
+
+/// oxygen breathing required from the inside tender of a treatment table
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
+pub struct TenderO2 {
+  /// depth of the last stop before the final ascent expressed in feet of
+  /// sea water
+  pub depth: u16,
+  /// oxygen breathed at the end of the last stop expressed in minutes
+  pub minutes: u16,
+  /// oxygen breathed at the end of the last stop when the table has been
+  /// extended more than the extension_threshold, expressed in minutes
+  pub extended_minutes: u16,
+  /// number of extensions above which extended_minutes apply
+  pub extension_threshold: u8,
+  /// the tender breathes oxygen during the final ascent to the surface
+  pub final_ascent: bool,
+  /// additional oxygen breathed at the end of the last stop when the tender
+  /// made a dive within the previous 18 hours, expressed in minutes
+  pub repetitive_minutes: u16,
+}
+
+/// a US Navy recompression treatment table
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TreatmentTable {
+  /// short code of the table (e.g TT6)
+  pub code: String,
+  /// oficially recognized name of the table
+  pub name: String,
+  /// the steps of the unextended table
+  pub steps: Vec<TreatmentStep>,
+  /// the extensions allowed by the table
+  pub extensions: Vec<TreatmentExtension>,
+  /// oxygen breathing required from the inside tender
+  pub tender_o2: TenderO2,
+}
+
+/// the US Navy recompression treatment tables
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TableTreatment {
+  /// represents a unique id for a table within the 
+  /// diving-decompression project.
+  pub table_code: String,
+  /// oficially recognized name for a table beyond the
+  /// diving-decompression project.
+  pub table_name: String,
+  /// table data
+  pub table_data: Vec::<TreatmentTable>,
+}
+
 /// nodeco_table() returns a typed and serialized US Navy air 
 /// no-decompression table from rev7 of the US Navy dive manual.
 pub fn nodeco_table() -> serde_json::Result<TableNdl> {
   let file = include_str!("JSON/usnavy-air-nodeco-rev7.json");
-  let sertab = serde_json::from_str(&file); 
-  return sertab;
+  serde_json::from_str(file)
 }
 
 /// deco_table() returns a typed and serialized US Navy air 
 /// decompression table from rev7 of the US Navy dive manual
 pub fn deco_table() -> serde_json::Result<TableAirDeco> {
   let file = include_str!("JSON/usnavy-air-deco-rev7.json");
-  let sertab = serde_json::from_str(&file); 
-  return sertab;
+  serde_json::from_str(file)
 }
 
 /// rgl_table() returns a typed and serialized US Navy repetitive group letter
 /// table from rev7 of the US Navy dive manual
 pub fn rgl_table() -> serde_json::Result<TableRgl> {
   let file = include_str!("JSON/usnavy-air-repetgroup-rev7.json");
-  let sertab = serde_json::from_str(&file); 
-  return sertab;
+  serde_json::from_str(file)
 }
 
 /// rnt_table() returns a typed and serialized US Navy residual nitrogen time
 /// table from rev7 of the US Navy dive manual
 pub fn rnt_table() -> serde_json::Result<TableRnt> {
   let file = include_str!("JSON/usnavy-air-rnt-rev7.json");
-  let sertab = serde_json::from_str(&file); 
-  return sertab;
-}
\ No newline at end of file
+  serde_json::from_str(file)
+}
+
+/// treatment_tables() returns the typed and serialized US Navy recompression
+/// treatment tables 5, 6, 6A, 4, 7 and 9 from rev7 of the US Navy dive manual
+pub fn treatment_tables() -> serde_json::Result<TableTreatment> {
+  let file = include_str!("JSON/usnavy-treatment-rev7.json");
+  serde_json::from_str(file)
+}
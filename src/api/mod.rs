@@ -0,0 +1,318 @@
+use std::fmt;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::{Dive, DivePlan};
+use crate::advisories::{Advised, Advisory, AdvisoryCode};
+use crate::airtables::RowDeco;
+use crate::errors::DiveError;
+
+/// HTTP status of a successful request
+pub const STATUS_OK: u16 = 200;
+
+/// HTTP status of a request that could not be read or holds invalid values
+pub const STATUS_BAD_REQUEST: u16 = 400;
+
+/// HTTP status of a request to an unknown path
+pub const STATUS_NOT_FOUND: u16 = 404;
+
+/// HTTP status of a request to a known path with the wrong method
+pub const STATUS_METHOD_NOT_ALLOWED: u16 = 405;
+
+/// HTTP status of a valid request whose dive is outside the tables
+pub const STATUS_UNPROCESSABLE_ENTITY: u16 = 422;
+
+/// the errors of a request that does not match any endpoint
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RouteError {
+  /// no endpoint is served at the path
+  NotFound {
+    /// the requested path
+    path: String,
+  },
+  /// the endpoint at the path does not accept the method
+  MethodNotAllowed {
+    /// the requested method
+    method: String,
+    /// the requested path
+    path: String,
+  },
+}
+
+/// a field of the JSON body of a request
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct EndpointField {
+  /// name of the field
+  pub name: String,
+  /// JSON type of the field
+  #[serde(rename = "type")]
+  pub kind: String,
+  /// the field must be present in the request
+  pub required: bool,
+  /// meaning and unit of the field
+  pub description: String,
+}
+
+/// an endpoint served by the API
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Endpoint {
+  /// HTTP method of the endpoint
+  pub method: String,
+  /// path of the endpoint
+  pub path: String,
+  /// what the endpoint returns
+  pub description: String,
+  /// the fields of the JSON body of the request, empty when the endpoint
+  /// takes no body
+  pub request: Vec<EndpointField>,
+}
+
+/// the machine readable description of the API
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ApiDescription {
+  /// name of the API
+  pub name: String,
+  /// version of the diving-decompression crate serving the API
+  pub version: String,
+  /// the endpoints served by the API
+  pub endpoints: Vec<Endpoint>,
+}
+
+/// the table results of a single dive
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DiveResult {
+  /// the dive of the request
+  pub dive: Dive,
+  /// no decompression limit at the depth of the dive along with its advisories
+  pub no_decompression_limit: Advised<u16>,
+  /// group letter at the end of the dive along with its advisories
+  pub group_letter: Advised<String>,
+}
+
+/// the table results of a dive plan
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DivePlanResult {
+  /// the dive plan of the request
+  pub plan: DivePlan,
+  /// no decompression limit of the first dive along with its advisories
+  pub no_decompression_limit: Advised<u16>,
+  /// group letter at the end of the first dive along with its advisories
+  pub group_letter: Advised<String>,
+  /// repetitive group letter after the surface interval along with its
+  /// advisories
+  pub repet_letter: Advised<String>,
+  /// residual nitrogen time of the next dive along with its advisories
+  pub residual_nitrogen_time: Advised<u16>,
+}
+
+/// the response to a request, the body is the JSON document to send
+#[derive(Clone, Debug, PartialEq)]
+pub struct ApiResponse {
+  /// HTTP status of the response
+  pub status: u16,
+  /// JSON body of the response
+  pub body: Value,
+}
+
+impl fmt::Display for RouteError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      RouteError::NotFound { path } => {
+        write!(f, "no endpoint is served at {}", path)
+      }
+      RouteError::MethodNotAllowed { method, path } => {
+        write!(f, "{} does not accept {} requests", path, method)
+      }
+    }
+  }
+}
+
+impl ApiResponse {
+  pub fn ok(value: &impl Serialize) -> Self {
+    //! Instantiates a successful ApiResponse object with the given value as body
+    Self {
+      status: STATUS_OK,
+      body: serde_json::to_value(value).expect("Error serializing the response"),
+    }
+  }
+
+  pub fn error<E: Serialize + fmt::Display>(status: u16, error: &E) -> Self {
+    //! Instantiates a failed ApiResponse object. the body holds the typed
+    //! error along with its message
+    Self {
+      status,
+      body: serde_json::json!({
+        "error": error,
+        "message": error.to_string(),
+      }),
+    }
+  }
+}
+
+/// description() returns the machine readable description of the endpoints
+pub fn description() -> ApiDescription {
+  let field = |name: &str, kind: &str, required: bool, description: &str| EndpointField {
+    name: String::from(name),
+    kind: String::from(kind),
+    required,
+    description: String::from(description),
+  };
+  let endpoint = |method: &str, path: &str, description: &str, request: Vec<EndpointField>| Endpoint {
+    method: String::from(method),
+    path: String::from(path),
+    description: String::from(description),
+    request,
+  };
+  let dive_fields = || vec![
+    field("depth", "integer", true, "depth of the dive expressed in feet of sea water"),
+    field("bottom_time", "integer", true, "bottom time of the dive expressed in minutes"),
+    field("exposure", "string", false, "none, cold, strenuous or cold_and_strenuous"),
+  ];
+
+  let mut plan_fields = dive_fields();
+  plan_fields.insert(2, field("surface_interval_time", "integer", true, "surface interval expressed in minutes"));
+  plan_fields.insert(3, field("next_dive_depth", "integer", true, "depth of the next dive expressed in feet of sea water"));
+
+  ApiDescription {
+    name: String::from("diving-decompression"),
+    version: String::from(env!("CARGO_PKG_VERSION")),
+    endpoints: vec![
+      endpoint("GET", "/health", "the status of the server", vec![]),
+      endpoint("GET", "/endpoints", "this description of the endpoints", vec![]),
+      endpoint("POST", "/dive", "the no decompression limit and group letter of a dive", dive_fields()),
+      endpoint("POST", "/dive-plan", "the no decompression limit, group letters and residual nitrogen time of a dive plan", plan_fields),
+      endpoint("POST", "/deco", "the air decompression schedule of a dive", dive_fields()),
+    ],
+  }
+}
+
+/// route() answers a request to the API. the path may carry a query,
+/// which is ignored, and the body is the JSON document of POST requests.
+/// a dive or dive plan outside the tables is answered with its typed error
+/// and STATUS_UNPROCESSABLE_ENTITY rather than with empty table results
+pub fn route(method: &str, path: &str, body: &str) -> ApiResponse {
+  let path = path.split('?').next().unwrap_or_default();
+
+  let endpoint = description().endpoints.into_iter().filter(|endpoint| endpoint.path == path).collect::<Vec<_>>();
+  if endpoint.is_empty() {
+    return ApiResponse::error(STATUS_NOT_FOUND, &RouteError::NotFound { path: String::from(path) });
+  }
+  if !endpoint.iter().any(|endpoint| endpoint.method.eq_ignore_ascii_case(method)) {
+    return ApiResponse::error(STATUS_METHOD_NOT_ALLOWED, &RouteError::MethodNotAllowed {
+      method: String::from(method),
+      path: String::from(path),
+    });
+  }
+
+  let result = match path {
+    "/health" => Ok(ApiResponse::ok(&serde_json::json!({ "status": "ok" }))),
+    "/endpoints" => Ok(ApiResponse::ok(&description())),
+    "/dive" => dive_request(body).map(|dive| {
+      let result = dive_result(dive);
+      within_tables(dive, &result.group_letter.advisories).map_or_else(outside_tables, |_| ApiResponse::ok(&result))
+    }),
+    "/dive-plan" => dive_plan_request(body).map(|plan| {
+      let result = dive_plan_result(plan);
+      let advisories = [&result.group_letter.advisories[..], &result.residual_nitrogen_time.advisories[..]].concat();
+      within_repetitive_tables(plan, &advisories).map_or_else(outside_tables, |_| ApiResponse::ok(&result))
+    }),
+    "/deco" => dive_request(body).map(|dive| {
+      let result = deco_result(dive);
+      within_tables(dive, &result.advisories).map_or_else(outside_tables, |_| ApiResponse::ok(&result))
+    }),
+    _ => Ok(ApiResponse::error(STATUS_NOT_FOUND, &RouteError::NotFound { path: String::from(path) })),
+  };
+
+  result.unwrap_or_else(|error| ApiResponse::error(STATUS_BAD_REQUEST, &error))
+}
+
+/// returns the error of a dive whose advisories place it outside the tables:
+/// deeper than the no-decompression table, or without a schedule in the air
+/// decompression table, no-decompression dives included
+fn within_tables(dive: Dive, advisories: &[Advisory]) -> Result<(), DiveError> {
+  let has = |code: AdvisoryCode| advisories.iter().any(|advisory| advisory.code == code);
+
+  if has(AdvisoryCode::OutsideNoDecompressionTable) {
+    return Err(DiveError::OutsideNoDecompressionTable { depth: dive.depth });
+  }
+  if has(AdvisoryCode::OutsideDecompressionTable) || has(AdvisoryCode::NoDecompressionRequired) {
+    return Err(DiveError::OutsideDecompressionTable { depth: dive.depth, bottom_time: dive.bottom_time });
+  }
+  Ok(())
+}
+
+/// returns the error of a dive plan whose advisories place it outside the
+/// tables, the repetitive dive tables included
+fn within_repetitive_tables(plan: DivePlan, advisories: &[Advisory]) -> Result<(), DiveError> {
+  let dive = Dive::new(plan.depth, plan.bottom_time).with_exposure(plan.exposure);
+  within_tables(dive, advisories)?;
+
+  let outside = advisories.iter().any(|advisory| {
+    advisory.code == AdvisoryCode::OutsideRepetitiveTables || advisory.code == AdvisoryCode::RepetitiveDiveNotPermitted
+  });
+  if outside {
+    return Err(DiveError::OutsideRepetitiveTables {
+      group_letter: dive.end_group_letter(),
+      surface_interval_time: plan.surface_interval_time,
+    });
+  }
+  Ok(())
+}
+
+fn outside_tables(error: DiveError) -> ApiResponse {
+  ApiResponse::error(STATUS_UNPROCESSABLE_ENTITY, &error)
+}
+
+/// dive_result() returns the table results of a single dive
+pub fn dive_result(dive: Dive) -> DiveResult {
+  DiveResult {
+    dive,
+    no_decompression_limit: dive.advised_no_decompression_limit(),
+    group_letter: dive.advised_group_letter(),
+  }
+}
+
+/// dive_plan_result() returns the table results of a dive plan
+pub fn dive_plan_result(plan: DivePlan) -> DivePlanResult {
+  DivePlanResult {
+    plan,
+    no_decompression_limit: plan.advised_no_decompression_limit(),
+    group_letter: plan.advised_group_letter(),
+    repet_letter: plan.advised_repet_letter(),
+    residual_nitrogen_time: plan.advised_residual_nitrogen_time(),
+  }
+}
+
+/// deco_result() returns the air decompression schedule of a dive
+pub fn deco_result(dive: Dive) -> Advised<RowDeco> {
+  dive.advised_deco_dive()
+}
+
+fn dive_request(body: &str) -> Result<Dive, DiveError> {
+  let dive: Dive = parse(body)?;
+  positive("depth", dive.depth)?;
+  positive("bottom_time", dive.bottom_time)?;
+  Ok(dive)
+}
+
+fn dive_plan_request(body: &str) -> Result<DivePlan, DiveError> {
+  let plan: DivePlan = parse(body)?;
+  positive("depth", plan.depth)?;
+  positive("bottom_time", plan.bottom_time)?;
+  positive("next_dive_depth", plan.next_dive_depth)?;
+  Ok(plan)
+}
+
+fn parse<T: DeserializeOwned>(body: &str) -> Result<T, DiveError> {
+  serde_json::from_str(body).map_err(|error| DiveError::InvalidRequest { message: error.to_string() })
+}
+
+fn positive(name: &str, value: u16) -> Result<(), DiveError> {
+  if value == 0 {
+    return Err(DiveError::InvalidRequest { message: format!("{} must be greater than 0", name) });
+  }
+  Ok(())
+}
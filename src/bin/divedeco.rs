@@ -0,0 +1,393 @@
+//! divedeco looks up the US Navy air tables rev7 from the command line
+//!
+//! exit codes: 0 on success, 1 when a plan file can not be read or
+//! evaluated, 2 on usage errors and 3 when the dive is outside the tables
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::io::Read;
+use std::process;
+
+use diving_decompression::{Dive, DivePlan, ExposureModifier};
+use diving_decompression::advisories::{Advised, Advisory, AdvisoryCode};
+use diving_decompression::airtables;
+use diving_decompression::dsl;
+use diving_decompression::profile::fsw;
+use diving_decompression::treatment::run_treatment;
+use diving_decompression::units::meters_to_feet;
+use serde::Serialize;
+
+/// the lookup succeeded
+const EXIT_OK: i32 = 0;
+/// the plan file could not be read or evaluated
+const EXIT_FAILURE: i32 = 1;
+/// the command line is invalid
+const EXIT_USAGE: i32 = 2;
+/// the dive is outside the tables
+const EXIT_OUTSIDE_TABLES: i32 = 3;
+
+const USAGE: &str = "usage: divedeco <command> [options]
+
+commands:
+  ndl    --depth D                                   no-decompression limit
+  group  --depth D --time T                          repetitive group at the end of a dive
+  repet  --depth D --time T --interval I --next-depth N
+                                                     repetitive group after the surface interval
+  rnt    --depth D --time T --interval I --next-depth N
+                                                     residual nitrogen time of the next dive
+  deco   --depth D --time T                          air decompression schedule
+  plan   FILE                                        evaluate a dive plan text, - reads stdin
+  tables [nodeco|deco|rgl|rnt|treatment]             print the tables
+
+options:
+  --units fsw|m    unit of the depths given on the command line (default fsw)
+  --cold           the diver was unusually cold during the dive
+  --strenuous      the workload during the dive was strenuous
+  --json           print the result as JSON
+  -h, --help       print this help
+
+times are in minutes, surface intervals in minutes or H:MM";
+
+/// an error ending the command with its exit code
+struct Failure {
+  code: i32,
+  message: String,
+}
+
+fn usage(message: impl Display) -> Failure {
+  Failure { code: EXIT_USAGE, message: format!("{}\n\n{}", message, USAGE) }
+}
+
+/// the options following the command
+struct Options {
+  values: HashMap<String, String>,
+  positional: Vec<String>,
+  json: bool,
+  meters: bool,
+  exposure: ExposureModifier,
+}
+
+impl Options {
+  fn parse(args: &[String]) -> Result<Self, Failure> {
+    let mut options = Options {
+      values: HashMap::new(),
+      positional: vec![],
+      json: false,
+      meters: false,
+      exposure: ExposureModifier::None,
+    };
+    let (mut cold, mut strenuous) = (false, false);
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+      match arg.as_str() {
+        "--json" => options.json = true,
+        "--cold" => cold = true,
+        "--strenuous" => strenuous = true,
+        "--depth" | "--time" | "--interval" | "--next-depth" | "--units" => {
+          let value = args.next().ok_or_else(|| usage(format!("{} needs a value", arg)))?;
+          options.values.insert(arg.trim_start_matches("--").to_string(), value.clone());
+        }
+        "-" => options.positional.push(arg.clone()),
+        option if option.starts_with('-') => return Err(usage(format!("unknown option {}", option))),
+        _ => options.positional.push(arg.clone()),
+      }
+    }
+
+    options.meters = match options.values.get("units").map(String::as_str) {
+      None | Some("fsw") | Some("ft") => false,
+      Some("m") | Some("msw") => true,
+      Some(units) => return Err(usage(format!("unknown units {}, expected fsw or m", units))),
+    };
+    options.exposure = match (cold, strenuous) {
+      (true, true) => ExposureModifier::ColdAndStrenuous,
+      (true, false) => ExposureModifier::Cold,
+      (false, true) => ExposureModifier::Strenuous,
+      (false, false) => ExposureModifier::None,
+    };
+
+    Ok(options)
+  }
+
+  fn depth(&self, name: &str) -> Result<u16, Failure> {
+    let text = self.values.get(name).ok_or_else(|| usage(format!("--{} is required", name)))?;
+    let depth: f64 = text.parse().ok()
+      .filter(|depth: &f64| depth.is_finite() && *depth >= 0.0 && *depth <= 10_000.0)
+      .ok_or_else(|| usage(format!("--{} expects a depth, found '{}'", name, text)))?;
+    Ok(fsw(if self.meters { meters_to_feet(depth) } else { depth }))
+  }
+
+  fn minutes(&self, name: &str) -> Result<u16, Failure> {
+    let text = self.values.get(name).ok_or_else(|| usage(format!("--{} is required", name)))?;
+    let minutes = match text.split_once(':') {
+      Some((hours, minutes)) if minutes.len() == 2 => hours.parse::<u16>().ok()
+        .zip(minutes.parse::<u16>().ok().filter(|minutes| *minutes < 60))
+        .and_then(|(hours, minutes)| hours.checked_mul(60)?.checked_add(minutes)),
+      Some(_) => None,
+      None => text.parse::<u16>().ok(),
+    };
+    minutes.ok_or_else(|| usage(format!("--{} expects minutes, found '{}'", name, text)))
+  }
+
+  fn dive(&self) -> Result<Dive, Failure> {
+    Ok(Dive::new(self.depth("depth")?, self.minutes("time")?).with_exposure(self.exposure))
+  }
+
+  fn dive_plan(&self) -> Result<DivePlan, Failure> {
+    let dive = self.dive()?;
+    Ok(DivePlan::from_dive(dive, self.minutes("interval")?, self.depth("next-depth")?))
+  }
+
+  fn expect_positional(&self, count: usize) -> Result<(), Failure> {
+    match self.positional.get(count) {
+      Some(extra) => Err(usage(format!("unexpected argument {}", extra))),
+      None => Ok(()),
+    }
+  }
+}
+
+fn main() {
+  let args: Vec<String> = std::env::args().skip(1).collect();
+
+  let code = match run(&args) {
+    Ok(code) => code,
+    Err(failure) => {
+      eprintln!("divedeco: {}", failure.message);
+      failure.code
+    }
+  };
+  process::exit(code);
+}
+
+fn run(args: &[String]) -> Result<i32, Failure> {
+  let command = match args.first().map(String::as_str) {
+    None => return Err(usage("a command is required")),
+    Some("-h") | Some("--help") | Some("help") => {
+      println!("{}", USAGE);
+      return Ok(EXIT_OK);
+    }
+    Some(command) => command,
+  };
+  let options = Options::parse(&args[1..])?;
+
+  match command {
+    "ndl" => {
+      options.expect_positional(0)?;
+      let depth = options.depth("depth")?;
+      let dive = Dive::new(depth, 0).with_exposure(options.exposure);
+      let ndl = dive.advised_no_decompression_limit();
+      let text = format!("no-decompression limit at {} fsw: {}", depth, minutes_text(ndl.value));
+      Ok(report(&options, &ndl, text, &ndl.advisories))
+    }
+    "group" => {
+      options.expect_positional(0)?;
+      let dive = options.dive()?;
+      let mut group = dive.advised_group_letter();
+      if group.has(AdvisoryCode::NoDecompressionLimitExceeded) || group.has(AdvisoryCode::OutsideNoDecompressionTable) {
+        let deco = dive.advised_deco_dive();
+        group = Advised::new(deco.value.repetgroup_letter, deco.advisories);
+      }
+      let letter = if group.has(AdvisoryCode::OutsideDecompressionTable) { "outside tables" } else { group.value.as_str() };
+      let text = format!("repetitive group after {} fsw for {} min: {}", dive.depth, dive.bottom_time, letter);
+      Ok(report(&options, &group, text, &group.advisories))
+    }
+    "repet" => {
+      options.expect_positional(0)?;
+      let plan = options.dive_plan()?;
+      let repet = plan.advised_repet_letter();
+      let text = format!("repetitive group after a {} min surface interval: {}", plan.surface_interval_time, repet.value);
+      Ok(report(&options, &repet, text, &repet.advisories))
+    }
+    "rnt" => {
+      options.expect_positional(0)?;
+      let plan = options.dive_plan()?;
+      let rnt = plan.advised_residual_nitrogen_time();
+      let text = format!("residual nitrogen time at {} fsw: {} min", plan.next_dive_depth, rnt.value);
+      Ok(report(&options, &rnt, text, &rnt.advisories))
+    }
+    "deco" => {
+      options.expect_positional(0)?;
+      let dive = options.dive()?;
+      let deco = dive.advised_deco_dive();
+      if deco.has(AdvisoryCode::NoDecompressionRequired) {
+        let group = dive.advised_group_letter();
+        let text = format!("no decompression required for {} fsw / {} min, repetitive group {}", dive.depth, dive.bottom_time, group.value);
+        let value = serde_json::json!({
+          "value": null,
+          "group": group.value,
+          "advisories": ([&group.advisories[..], &deco.advisories[..]].concat()),
+        });
+        return Ok(report(&options, &value, text, &group.advisories));
+      }
+      let schedule = &deco.value;
+      let mut stops = schedule.air_deco_stops.clone();
+      stops.sort_by_key(|stop| std::cmp::Reverse(stop.depth));
+      if deco.has(AdvisoryCode::OutsideDecompressionTable) {
+        let text = format!("no air decompression schedule for {} fsw / {} min", dive.depth, dive.bottom_time);
+        return Ok(report(&options, &deco, text, &deco.advisories));
+      }
+      let mut text = format!("air decompression for {} fsw / {} min\n", dive.depth, dive.bottom_time);
+      text.push_str(&format!("  schedule: {} - {} min\n", schedule.min_time, schedule.max_time));
+      for stop in stops.iter() {
+        text.push_str(&format!("  stop {} fsw: {} min\n", stop.depth, stop.time));
+      }
+      text.push_str(&format!("  total ascent time: {} (air) {} (in-water O2)\n", schedule.air_tat, schedule.o2_tat));
+      text.push_str(&format!("  chamber O2 periods: {}\n", schedule.o2cp));
+      text.push_str(&format!("  repetitive group: {}", schedule.repetgroup_letter));
+      Ok(report(&options, &deco, text, &deco.advisories))
+    }
+    "plan" => plan(&options),
+    "tables" => tables(&options),
+    command => Err(usage(format!("unknown command {}", command))),
+  }
+}
+
+fn plan(options: &Options) -> Result<i32, Failure> {
+  options.expect_positional(1)?;
+  let path = options.positional.first().ok_or_else(|| usage("plan needs a FILE"))?;
+  let text = if path == "-" {
+    let mut text = String::new();
+    std::io::stdin().read_to_string(&mut text)
+      .map(|_| text)
+      .map_err(|error| Failure { code: EXIT_FAILURE, message: format!("stdin: {}", error) })?
+  } else {
+    std::fs::read_to_string(path)
+      .map_err(|error| Failure { code: EXIT_FAILURE, message: format!("{}: {}", path, error) })?
+  };
+
+  let evaluations = dsl::evaluate(&text)
+    .map_err(|error| Failure { code: EXIT_FAILURE, message: format!("{}:{}", path, error) })?;
+
+  let mut lines = vec![];
+  let mut advisories = vec![];
+  for (index, evaluation) in evaluations.iter().enumerate() {
+    let dive = evaluation.dive;
+    let mut line = format!("dive {}: {} fsw / {} min", index + 1, dive.depth, dive.bottom_time);
+    if let (Some(repet), Some(rnt)) = (&evaluation.repet_letter, &evaluation.residual_nitrogen_time) {
+      line.push_str(&format!(", starts in group {} with {} min residual nitrogen", repet.value, rnt.value));
+      advisories.extend(repet.advisories.iter().chain(rnt.advisories.iter()).cloned());
+    }
+    match &evaluation.deco {
+      Some(deco) => {
+        line.push_str(&format!(", decompression {} - {} min, ends in group {}", deco.value.min_time, deco.value.max_time, deco.value.repetgroup_letter));
+        advisories.extend(deco.advisories.iter().cloned());
+      }
+      None => line.push_str(&format!(", no decompression, ends in group {}", evaluation.group_letter.value)),
+    }
+    advisories.extend(evaluation.group_letter.advisories.iter().cloned());
+    lines.push(line);
+  }
+
+  Ok(report(options, &evaluations, lines.join("\n"), &advisories))
+}
+
+fn tables(options: &Options) -> Result<i32, Failure> {
+  options.expect_positional(1)?;
+  let table_error = |error: serde_json::Error| Failure { code: EXIT_FAILURE, message: error.to_string() };
+
+  let name = match options.positional.first() {
+    Some(name) => name.as_str(),
+    None => {
+      println!("nodeco     {}", airtables::nodeco_table().map_err(table_error)?.table_name);
+      println!("deco       {}", airtables::deco_table().map_err(table_error)?.table_name);
+      println!("rgl        {}", airtables::rgl_table().map_err(table_error)?.table_name);
+      println!("rnt        {}", airtables::rnt_table().map_err(table_error)?.table_name);
+      println!("treatment  {}", airtables::treatment_tables().map_err(table_error)?.table_name);
+      return Ok(EXIT_OK);
+    }
+  };
+
+  let mut lines = vec![];
+  let code = match name {
+    "nodeco" => {
+      let table = airtables::nodeco_table().map_err(table_error)?;
+      for row in table.table_data.iter() {
+        let ndl = if row.unlimited { String::from("unlimited") } else { format!("{} min", row.no_stop_limit) };
+        lines.push(format!("{}-{} fsw: {}", row.min_fsw, row.max_fsw, ndl));
+      }
+      print_table(options, &table, &lines)
+    }
+    "deco" => {
+      let table = airtables::deco_table().map_err(table_error)?;
+      for depth in table.table_data.iter() {
+        for row in depth.rows.iter() {
+          let stops: Vec<String> = row.air_deco_stops.iter().map(|stop| format!("{}/{}", stop.depth, stop.time)).collect();
+          lines.push(format!("{}-{} fsw {}-{} min: stops {} group {}", depth.min_fsw, depth.max_fsw, row.min_time, row.max_time, stops.join(" "), row.repetgroup_letter));
+        }
+      }
+      print_table(options, &table, &lines)
+    }
+    "rgl" => {
+      let table = airtables::rgl_table().map_err(table_error)?;
+      for row in table.table_data.iter() {
+        lines.push(format!("{} after {}-{} min: {}", row.group_letter, row.min_time, row.max_time, row.repet_letter));
+      }
+      print_table(options, &table, &lines)
+    }
+    "rnt" => {
+      let table = airtables::rnt_table().map_err(table_error)?;
+      for row in table.table_data.iter() {
+        let rnt: Vec<String> = row.rnt.iter().map(|rnt| format!("{}-{} fsw {}", rnt.min_depth, rnt.max_depth, rnt.rnt)).collect();
+        lines.push(format!("{}: {}", row.repet_letter, rnt.join(", ")));
+      }
+      print_table(options, &table, &lines)
+    }
+    "treatment" => {
+      let table = airtables::treatment_tables().map_err(table_error)?;
+      for treatment in table.table_data.iter() {
+        let total = run_treatment(treatment, &[]).map(|schedule| schedule.total_time).unwrap_or(0);
+        lines.push(format!("{}: {} ({} min)", treatment.code, treatment.name, total));
+      }
+      print_table(options, &table, &lines)
+    }
+    name => return Err(usage(format!("unknown table {}", name))),
+  };
+
+  Ok(code)
+}
+
+/// prints a table as JSON or as its human readable lines
+fn print_table<T: Serialize>(options: &Options, table: &T, lines: &[String]) -> i32 {
+  if options.json {
+    println!("{}", serde_json::to_string_pretty(table).unwrap_or_default());
+  } else {
+    for line in lines.iter() {
+      println!("{}", line);
+    }
+  }
+  EXIT_OK
+}
+
+/// prints the result as JSON or as human readable text followed by its
+/// advisories and returns the exit code of the result
+fn report<T: Serialize>(options: &Options, value: &T, text: String, advisories: &[Advisory]) -> i32 {
+  if options.json {
+    println!("{}", serde_json::to_string_pretty(value).unwrap_or_default());
+  } else {
+    println!("{}", text);
+    let mut reported: Vec<AdvisoryCode> = vec![];
+    for advisory in advisories.iter() {
+      if !reported.contains(&advisory.code) {
+        reported.push(advisory.code);
+        println!("{:?}: {} ({})", advisory.severity, advisory.message, advisory.reference);
+      }
+    }
+  }
+
+  let outside = advisories.iter().any(|advisory| matches!(
+    advisory.code,
+    AdvisoryCode::OutsideNoDecompressionTable
+      | AdvisoryCode::OutsideDecompressionTable
+      | AdvisoryCode::OutsideRepetitiveTables
+      | AdvisoryCode::RepetitiveDiveNotPermitted
+  ));
+  if outside { EXIT_OUTSIDE_TABLES } else { EXIT_OK }
+}
+
+fn minutes_text(minutes: u16) -> String {
+  if minutes == 9999 {
+    String::from("unlimited")
+  } else {
+    format!("{} min", minutes)
+  }
+}
@@ -0,0 +1,91 @@
+//! diveserver serves the JSON API of the air tables over HTTP on localhost.
+//! GET /endpoints describes the endpoints it serves
+//!
+//! usage: diveserver [--port PORT], the port defaults to 8080
+//!
+//! Ctrl-C (SIGINT), SIGTERM and SIGHUP stop the server once the request in
+//! progress is answered
+
+use std::io::Read;
+use std::process;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use diving_decompression::api::{self, ApiResponse, STATUS_BAD_REQUEST};
+use diving_decompression::errors::DiveError;
+use tiny_http::{Header, Request, Response, Server};
+
+/// port served when none is given on the command line
+const DEFAULT_PORT: u16 = 8080;
+
+/// largest request body accepted expressed in bytes
+const MAX_BODY: u64 = 64 * 1024;
+
+/// how often the server checks for a shutdown request
+const SHUTDOWN_POLL: Duration = Duration::from_millis(250);
+
+fn main() {
+  let port = match port(std::env::args().skip(1).collect()) {
+    Ok(port) => port,
+    Err(message) => {
+      eprintln!("diveserver: {}\n\nusage: diveserver [--port PORT]", message);
+      process::exit(2);
+    }
+  };
+
+  let server = match Server::http(("127.0.0.1", port)) {
+    Ok(server) => server,
+    Err(error) => {
+      eprintln!("diveserver: can not listen on port {}: {}", port, error);
+      process::exit(1);
+    }
+  };
+
+  let running = Arc::new(AtomicBool::new(true));
+  let handler = Arc::clone(&running);
+  if let Err(error) = ctrlc::set_handler(move || handler.store(false, Ordering::SeqCst)) {
+    eprintln!("diveserver: {}", error);
+    process::exit(1);
+  }
+
+  eprintln!("diveserver: listening on http://127.0.0.1:{}", port);
+  while running.load(Ordering::SeqCst) {
+    match server.recv_timeout(SHUTDOWN_POLL) {
+      Ok(Some(request)) => respond(request),
+      Ok(None) => {}
+      Err(error) => {
+        eprintln!("diveserver: {}", error);
+        break;
+      }
+    }
+  }
+  eprintln!("diveserver: shutting down");
+}
+
+fn port(args: Vec<String>) -> Result<u16, String> {
+  match args.as_slice() {
+    [] => Ok(DEFAULT_PORT),
+    [option, port] if option == "--port" => port.parse().map_err(|_| format!("'{}' is not a port", port)),
+    _ => Err(format!("unexpected arguments {}", args.join(" "))),
+  }
+}
+
+fn respond(mut request: Request) {
+  let mut body = String::new();
+  let response = match request.as_reader().take(MAX_BODY + 1).read_to_string(&mut body) {
+    Ok(length) if length as u64 > MAX_BODY => ApiResponse::error(STATUS_BAD_REQUEST, &DiveError::InvalidRequest {
+      message: format!("the body exceeds {} bytes", MAX_BODY),
+    }),
+    Ok(_) => api::route(request.method().as_str(), request.url(), &body),
+    Err(error) => ApiResponse::error(STATUS_BAD_REQUEST, &DiveError::InvalidRequest { message: error.to_string() }),
+  };
+
+  let content_type = Header::from_bytes("Content-Type", "application/json").expect("Error building the content type header");
+  let response = Response::from_string(response.body.to_string())
+    .with_status_code(response.status)
+    .with_header(content_type);
+  if let Err(error) = request.respond(response) {
+    eprintln!("diveserver: {}", error);
+  }
+}
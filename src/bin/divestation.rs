@@ -0,0 +1,172 @@
+//! divestation is the full-screen timekeeper of the dive station. every
+//! diver is given on the command line as NAME:DEPTH, the depth in feet of
+//! sea water, and the timekeeper records the events of the selected diver
+//! from the keyboard
+
+use std::io::{self, Write};
+use std::process;
+use std::time::{Duration, Instant};
+
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::Print;
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+use diving_decompression::station::{Diver, DiverView, StationEvent};
+
+/// refresh period of the screen
+const TICK: Duration = Duration::from_millis(250);
+
+/// depth change of the + and - keys expressed in feet of sea water
+const DEPTH_STEP: u16 = 10;
+
+const KEYS: &str = "s LS  b RB  l LB  t reached stop  n left stop  r RS  +/- depth  up/down select  q quit";
+
+/// restores the terminal when the station closes
+struct Screen;
+
+impl Screen {
+  fn open() -> io::Result<Self> {
+    terminal::enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen, Hide)?;
+    Ok(Screen)
+  }
+}
+
+impl Drop for Screen {
+  fn drop(&mut self) {
+    let _ = execute!(io::stdout(), Show, LeaveAlternateScreen);
+    let _ = terminal::disable_raw_mode();
+  }
+}
+
+fn main() {
+  let divers = match parse_divers(std::env::args().skip(1)) {
+    Ok(divers) => divers,
+    Err(message) => {
+      eprintln!("divestation: {}\n\nusage: divestation NAME:DEPTH [NAME:DEPTH ...]\n\nkeys: {}", message, KEYS);
+      process::exit(2);
+    }
+  };
+
+  if let Err(error) = run(divers) {
+    eprintln!("divestation: {}", error);
+    process::exit(1);
+  }
+}
+
+fn parse_divers(args: impl Iterator<Item = String>) -> Result<Vec<Diver>, String> {
+  let divers = args
+    .map(|arg| {
+      let (name, depth) = arg.rsplit_once(':').ok_or_else(|| format!("'{}' is not NAME:DEPTH", arg))?;
+      let depth = depth.parse::<u16>().ok().filter(|depth| *depth > 0).ok_or_else(|| format!("'{}' is not a depth in fsw", depth))?;
+      Ok(Diver::new(name, depth))
+    })
+    .collect::<Result<Vec<_>, String>>()?;
+
+  if divers.is_empty() {
+    return Err(String::from("at least one diver is required"));
+  }
+  Ok(divers)
+}
+
+fn run(mut divers: Vec<Diver>) -> io::Result<()> {
+  let _screen = Screen::open()?;
+  let start = Instant::now();
+  let mut selected = 0;
+  let mut message = String::new();
+
+  loop {
+    let now = start.elapsed().as_secs() as u32;
+    draw(&divers, selected, now, &message)?;
+
+    if !event::poll(TICK)? {
+      continue;
+    }
+    let key = match event::read()? {
+      Event::Key(key @ KeyEvent { kind: KeyEventKind::Press, .. }) => key,
+      _ => continue,
+    };
+
+    let station_event = match key.code {
+      KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+      KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Ok(()),
+      KeyCode::Up | KeyCode::Char('k') => {
+        selected = selected.saturating_sub(1);
+        None
+      }
+      KeyCode::Down | KeyCode::Char('j') => {
+        selected = (selected + 1).min(divers.len() - 1);
+        None
+      }
+      KeyCode::Char('+') | KeyCode::Char('-') => {
+        let depth = divers[selected].view(now).depth;
+        let depth = if key.code == KeyCode::Char('+') { depth.saturating_add(DEPTH_STEP) } else { depth.saturating_sub(DEPTH_STEP) };
+        message = divers[selected].set_depth(depth).err().map_or_else(String::new, |error| error.to_string());
+        None
+      }
+      KeyCode::Char('s') => Some(StationEvent::LeftSurface),
+      KeyCode::Char('b') => Some(StationEvent::ReachedBottom),
+      KeyCode::Char('l') => Some(StationEvent::LeftBottom),
+      KeyCode::Char('t') => Some(StationEvent::ReachedStop),
+      KeyCode::Char('n') => Some(StationEvent::LeftStop),
+      KeyCode::Char('r') => Some(StationEvent::ReachedSurface),
+      _ => None,
+    };
+
+    if let Some(station_event) = station_event {
+      message = match divers[selected].handle(station_event, now) {
+        Ok(()) => String::new(),
+        Err(error) => error.to_string(),
+      };
+    }
+  }
+}
+
+fn draw(divers: &[Diver], selected: usize, now: u32, message: &str) -> io::Result<()> {
+  let mut stdout = io::stdout();
+  let mut lines = vec![
+    format!("DIVE STATION  {}", clock(now)),
+    String::new(),
+    format!("  {:<12} {:<18} {:>7}  {:>8}  {:>9}  {:<5}  STOPS", "DIVER", "STATUS", "DEPTH", "TIMER", "NDL LEFT", "GROUP"),
+  ];
+
+  for (index, diver) in divers.iter().enumerate() {
+    let view = diver.view(now);
+    let marker = if index == selected { '>' } else { ' ' };
+    lines.push(format!(
+      "{} {:<12} {:<18} {:>3} fsw  {:>8}  {:>9}  {:<5}  {}",
+      marker,
+      view.name,
+      view.status.to_string(),
+      view.depth,
+      clock(view.timer),
+      view.remaining_no_decompression.map_or_else(String::new, |minutes| format!("{} min", minutes)),
+      view.group_letter.clone().unwrap_or_default(),
+      stops(&view),
+    ));
+  }
+
+  lines.push(String::new());
+  lines.push(String::from(KEYS));
+  lines.push(String::from(message));
+
+  queue!(stdout, MoveTo(0, 0), Clear(ClearType::All))?;
+  for (row, line) in lines.iter().enumerate() {
+    queue!(stdout, MoveTo(0, row as u16), Print(line))?;
+  }
+  stdout.flush()
+}
+
+/// returns the stop countdowns of the diver, the current stop marked with *
+fn stops(view: &DiverView) -> String {
+  view.stops.iter()
+    .map(|stop| format!("{}/{}{}", stop.depth, clock(stop.remaining), if stop.current { "*" } else { "" }))
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+/// returns the seconds written as H:MM:SS
+fn clock(seconds: u32) -> String {
+  format!("{}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+}
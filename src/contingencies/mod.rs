@@ -0,0 +1,184 @@
+use crate::airtables::{DecoStops, RowDeco};
+use crate::errors::DiveError;
+
+/// deepest stop at which oxygen is breathed during in-water decompression
+/// expressed in feet of sea water. deeper stops of an in-water oxygen
+/// schedule are always breathed on air.
+pub const O2_STOP_MAX_DEPTH: u16 = 30;
+
+/// every minute of oxygen stop time left when the oxygen supply fails
+/// is replaced by this many minutes of air stop time at the same depth
+pub const O2_TO_AIR_FACTOR: u16 = 3;
+
+/// procedure to follow after losing the oxygen supply during an in-water
+/// oxygen decompression
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "procedure", rename_all = "snake_case")]
+pub enum O2LossProcedure {
+  /// shift to air and complete the remaining obligation in the water
+  AirDecompression {
+    /// oxygen stop time left when the supply failed expressed in minutes
+    remaining_o2_time: u16,
+    /// the air stops that replace the remaining oxygen stops, the stop at
+    /// the depth of the failure is reduced by the oxygen time already breathed
+    air_deco_stops: Vec<DecoStops>,
+  },
+  /// the schedule does not allow in-water air decompression, surface and
+  /// complete the decompression with the SurDO2 protocol
+  SurDO2 {
+    /// oxygen stop time left when the supply failed expressed in minutes
+    remaining_o2_time: u16,
+    /// number of chamber periods of the schedule
+    o2cp: f32,
+  },
+}
+
+/// o2_supply_loss() returns the procedure to follow when the oxygen supply
+/// fails at the `failure_depth` stop of an in-water oxygen schedule after
+/// `o2_time` minutes of oxygen breathing at that stop. stops deeper than
+/// the failure depth are considered completed.
+///
+/// the remaining oxygen time of every stop is converted to air time using
+/// O2_TO_AIR_FACTOR, never exceeding the air stop time prescribed by the
+/// air decompression schedule for the same depth. schedules that require
+/// SurDO2 are shifted to SurDO2 instead.
+pub fn o2_supply_loss(schedule: &RowDeco, failure_depth: u16, o2_time: u16) -> Result<O2LossProcedure, DiveError> {
+  let failed_stop = schedule.o2_deco_stops.iter()
+    .find(|stop| stop.depth == failure_depth && stop.depth <= O2_STOP_MAX_DEPTH)
+    .ok_or(DiveError::StopNotInSchedule { depth: failure_depth })?;
+
+  if o2_time > failed_stop.time {
+    return Err(DiveError::StopTimeExceeded {
+      depth: failure_depth,
+      time: o2_time,
+      scheduled: failed_stop.time,
+    });
+  }
+
+  let remaining: Vec<DecoStops> = schedule.o2_deco_stops.iter()
+    .filter(|stop| stop.depth <= failure_depth)
+    .map(|stop| DecoStops {
+      depth: stop.depth,
+      time: if stop.depth == failure_depth { stop.time - o2_time } else { stop.time },
+    })
+    .collect();
+
+  let remaining_o2_time = remaining.iter().map(|stop| stop.time).sum();
+
+  if schedule.strict_surdo2 || schedule.surdo2_required {
+    return Ok(O2LossProcedure::SurDO2 { remaining_o2_time, o2cp: schedule.o2cp });
+  }
+
+  let air_deco_stops = remaining.iter()
+    .filter(|stop| stop.time > 0)
+    .map(|stop| {
+      let air_time = schedule.air_deco_stops.iter()
+        .find(|air_stop| air_stop.depth == stop.depth)
+        .map_or(0, |air_stop| air_stop.time);
+      DecoStops {
+        depth: stop.depth,
+        time: (stop.time * O2_TO_AIR_FACTOR).min(air_time),
+      }
+    })
+    .collect();
+
+  Ok(O2LossProcedure::AirDecompression { remaining_o2_time, air_deco_stops })
+}
+
+/// longest surface interval after a blowup that still allows returning to
+/// the omitted stop expressed in seconds
+pub const BLOWUP_RETURN_INTERVAL: u32 = 60;
+
+/// longest surface interval after a blowup that still allows completing
+/// the decompression with the SurDO2 protocol expressed in seconds
+pub const BLOWUP_SURDO2_INTERVAL: u32 = 7 * 60;
+
+/// stop time added to the omitted stop when the diver returns to it
+/// expressed in minutes
+pub const BLOWUP_STOP_EXTENSION: u16 = 1;
+
+/// most chamber periods of the original schedule treated on Treatment
+/// Table 5 when the omitted decompression can no longer be completed with
+/// SurDO2, schedules with more chamber periods are treated on Treatment
+/// Table 6 (US Navy Diving Manual rev7, table 9-6)
+pub const TT5_CHAMBER_PERIODS: f32 = 2.0;
+
+/// time the diver is observed on the surface after a blowup that omitted
+/// no decompression expressed in minutes
+pub const BLOWUP_OBSERVATION_TIME: u16 = 60;
+
+/// a step of the procedure to follow after an uncontrolled ascent
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum BlowupAction {
+  /// keep the diver on the surface and observe for symptoms
+  ObserveOnSurface {
+    /// observation time expressed in minutes
+    minutes: u16,
+  },
+  /// return the diver to the deepest omitted stop
+  ReturnToStop {
+    /// depth of the stop expressed in feet of sea water
+    depth: u16,
+    /// stop time added to the stop expressed in minutes
+    additional_time: u16,
+  },
+  /// resume the decompression of the original schedule
+  ResumeDecompression {
+    /// the air stops left to complete, the stop the diver returned to
+    /// already includes the additional time
+    air_deco_stops: Vec<DecoStops>,
+  },
+  /// complete the decompression in the chamber with the SurDO2 protocol
+  SurDO2 {
+    /// number of chamber periods of the schedule
+    o2cp: f32,
+  },
+  /// recompress the diver on a treatment table
+  Treatment {
+    /// code of the treatment table
+    table: String,
+  },
+}
+
+/// blowup() returns the procedure to follow after an uncontrolled ascent
+/// to the surface from `blowup_depth` while decompressing on the air
+/// schedule, once the diver spent `surface_interval` seconds on the
+/// surface. a recompression chamber is assumed to be on station.
+///
+/// the deepest omitted stop is the deepest stop at or above the depth of
+/// the blowup and the stop the diver was on is considered entirely
+/// omitted. these are the procedures of table 9-6 of the US Navy Diving
+/// Manual rev7 for a diver without symptoms, a symptomatic diver is
+/// treated for decompression sickness instead.
+pub fn blowup(schedule: &RowDeco, blowup_depth: u16, surface_interval: u32) -> Vec<BlowupAction> {
+  let mut omitted: Vec<DecoStops> = schedule.air_deco_stops.iter()
+    .filter(|stop| stop.depth <= blowup_depth && stop.time > 0)
+    .cloned()
+    .collect();
+  omitted.sort_by_key(|stop| std::cmp::Reverse(stop.depth));
+
+  let deepest = match omitted.first() {
+    Some(stop) => stop.depth,
+    None => return vec![BlowupAction::ObserveOnSurface { minutes: BLOWUP_OBSERVATION_TIME }],
+  };
+
+  if surface_interval < BLOWUP_RETURN_INTERVAL {
+    omitted[0].time += BLOWUP_STOP_EXTENSION;
+    return vec![
+      BlowupAction::ReturnToStop { depth: deepest, additional_time: BLOWUP_STOP_EXTENSION },
+      BlowupAction::ResumeDecompression { air_deco_stops: omitted },
+    ];
+  }
+
+  if deepest > O2_STOP_MAX_DEPTH {
+    return vec![BlowupAction::Treatment { table: String::from("TT6") }];
+  }
+
+  if surface_interval <= BLOWUP_SURDO2_INTERVAL {
+    return vec![BlowupAction::SurDO2 { o2cp: schedule.o2cp }];
+  }
+
+  let table = if schedule.o2cp <= TT5_CHAMBER_PERIODS { "TT5" } else { "TT6" };
+  vec![BlowupAction::Treatment { table: String::from(table) }]
+}
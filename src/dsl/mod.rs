@@ -0,0 +1,301 @@
+use crate::{Dive, DivePlan, ExposureModifier};
+use crate::advisories::Advised;
+use crate::airtables::RowDeco;
+use crate::errors::DiveError;
+use crate::formats::{annotate, dive_plans, LoggedDive, PlannedDive};
+use crate::profile::fsw;
+use crate::units::meters_to_feet;
+
+/// a statement of a dive plan text along with its position
+#[derive(Serialize, Deserialize, Copy, Clone, Debug)]
+pub struct Statement {
+  /// what the statement describes
+  pub kind: StatementKind,
+  /// line of the statement in the text, starting at 1
+  pub line: usize,
+  /// column of the statement in the line, starting at 1
+  pub column: usize,
+}
+
+/// the statements of the dive plan language
+#[derive(Serialize, Deserialize, Copy, Clone, Debug)]
+#[serde(tag = "statement", rename_all = "snake_case")]
+pub enum StatementKind {
+  /// `dive 60fsw 40min [cold] [strenuous]`
+  Dive {
+    /// the dive as written
+    dive: Dive,
+  },
+  /// `surface 1h30`
+  Surface {
+    /// surface interval expressed in minutes
+    minutes: u16,
+  },
+}
+
+/// the dives of a day along with the surface intervals between them
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DayPlan {
+  /// the dives in the order they are made
+  pub dives: Vec<Dive>,
+  /// the surface interval before every dive but the first expressed in
+  /// minutes
+  pub surface_intervals: Vec<u16>,
+}
+
+/// the table results of a dive of a day plan
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DiveEvaluation {
+  /// the dive as written
+  pub dive: Dive,
+  /// the single dive the dive counts as on the tables, the residual
+  /// nitrogen time added to the bottom time of a repetitive dive
+  pub equivalent_dive: Dive,
+  /// repetitive group letter at the start of the dive, only for
+  /// repetitive dives
+  pub repet_letter: Option<Advised<String>>,
+  /// residual nitrogen time charged to the dive expressed in minutes, only
+  /// for repetitive dives
+  pub residual_nitrogen_time: Option<Advised<u16>>,
+  /// no decompression limit at the depth of the dive expressed in minutes
+  pub no_decompression_limit: u16,
+  /// repetitive group letter at the end of the equivalent dive
+  pub group_letter: Advised<String>,
+  /// the air decompression schedule of the equivalent dive, only when the
+  /// bottom time exceeds the no decompression limit
+  pub deco: Option<Advised<RowDeco>>,
+}
+
+/// parse() reads the statements of a dive plan text. statements are
+/// separated by semicolons or new lines and `#` starts a comment running
+/// to the end of the line:
+///
+/// - `dive <depth> <bottom time> [cold] [strenuous]` where the depth is
+///   written in fsw, ft or m, for example 60fsw or 18m
+/// - `surface <interval>` where the interval is written as 1h30, 2h or 45min
+///
+/// errors give the line and column where parsing failed
+pub fn parse(text: &str) -> Result<Vec<Statement>, DiveError> {
+  let mut statements = vec![];
+
+  for (index, line) in text.lines().enumerate() {
+    let line_number = index + 1;
+    let code = line.split('#').next().unwrap_or("");
+
+    let mut offset = 0;
+    for part in code.split(';') {
+      let words = words(part, offset);
+      offset += part.chars().count() + 1;
+
+      let (keyword, column) = match words.first() {
+        Some(word) => *word,
+        None => continue,
+      };
+      let error = |column: usize, message: String| DiveError::Syntax { line: line_number, column, message };
+      let argument = |position: usize, expected: &str| {
+        words.get(position).copied().ok_or_else(|| {
+          let column = words.last().map_or(column, |(word, column)| column + word.chars().count());
+          error(column, format!("expected {}", expected))
+        })
+      };
+
+      let kind = match keyword {
+        "dive" => {
+          let (depth_word, depth_column) = argument(1, "a depth such as 60fsw")?;
+          let depth = depth(depth_word).ok_or_else(|| error(depth_column, format!("'{}' is not a depth such as 60fsw or 18m", depth_word)))?;
+          let (time_word, time_column) = argument(2, "a bottom time such as 40min")?;
+          let bottom_time = duration(time_word).ok_or_else(|| error(time_column, format!("'{}' is not a bottom time such as 40min", time_word)))?;
+
+          let (mut cold, mut strenuous) = (false, false);
+          for (word, column) in words[3..].iter() {
+            match *word {
+              "cold" if !cold => cold = true,
+              "strenuous" if !strenuous => strenuous = true,
+              _ => return Err(error(*column, format!("unexpected '{}'", word))),
+            }
+          }
+          let exposure = match (cold, strenuous) {
+            (true, true) => ExposureModifier::ColdAndStrenuous,
+            (true, false) => ExposureModifier::Cold,
+            (false, true) => ExposureModifier::Strenuous,
+            (false, false) => ExposureModifier::None,
+          };
+
+          StatementKind::Dive { dive: Dive::new(depth, bottom_time).with_exposure(exposure) }
+        }
+        "surface" => {
+          let (word, column) = argument(1, "a surface interval such as 1h30")?;
+          let minutes = duration(word).ok_or_else(|| error(column, format!("'{}' is not a surface interval such as 1h30", word)))?;
+          if let Some((word, column)) = words.get(2) {
+            return Err(error(*column, format!("unexpected '{}'", word)));
+          }
+          StatementKind::Surface { minutes }
+        }
+        _ => return Err(error(column, format!("expected 'dive' or 'surface', found '{}'", keyword))),
+      };
+
+      statements.push(Statement { kind, line: line_number, column });
+    }
+  }
+
+  Ok(statements)
+}
+
+/// parse_day() reads a dive plan text into the dives of a day. the text
+/// starts with a dive and every following dive comes after a surface
+/// interval, a trailing surface interval is allowed
+pub fn parse_day(text: &str) -> Result<DayPlan, DiveError> {
+  let mut plan = DayPlan { dives: vec![], surface_intervals: vec![] };
+  let mut interval: Option<u16> = None;
+
+  for statement in parse(text)? {
+    let error = |message: &str| DiveError::Syntax {
+      line: statement.line,
+      column: statement.column,
+      message: String::from(message),
+    };
+
+    match statement.kind {
+      StatementKind::Dive { dive } => {
+        match (plan.dives.is_empty(), interval.take()) {
+          (true, _) => {}
+          (false, Some(minutes)) => plan.surface_intervals.push(minutes),
+          (false, None) => return Err(error("a dive must follow a surface interval")),
+        }
+        plan.dives.push(dive);
+      }
+      StatementKind::Surface { minutes } => {
+        if plan.dives.is_empty() {
+          return Err(error("the plan must start with a dive"));
+        }
+        if interval.is_some() {
+          return Err(error("a surface interval must follow a dive"));
+        }
+        interval = Some(minutes);
+      }
+    }
+  }
+
+  if plan.dives.is_empty() {
+    return Err(DiveError::Syntax { line: 1, column: 1, message: String::from("the plan has no dive") });
+  }
+
+  Ok(plan)
+}
+
+/// evaluate() reads a dive plan text and returns the table results of
+/// every dive of the day
+pub fn evaluate(text: &str) -> Result<Vec<DiveEvaluation>, DiveError> {
+  Ok(parse_day(text)?.evaluate())
+}
+
+impl DayPlan {
+  pub fn logged_dives(&self) -> Vec<LoggedDive> {
+    //! returns the dives of the day made as planned, every dive but the
+    //! first following its surface interval
+    self.dives.iter().enumerate()
+      .map(|(index, dive)| {
+        let planned = match index.checked_sub(1) {
+          Some(previous) => PlannedDive::new(*dive).with_surface_interval(u32::from(self.surface_intervals[previous]) * 60),
+          None => PlannedDive::new(*dive),
+        };
+        planned.logged()
+      })
+      .collect()
+  }
+
+  pub fn plans(&self) -> Vec<DivePlan> {
+    //! returns the DivePlan chain of the day, one plan for every surface
+    //! interval. the first dive of every plan but the first is the
+    //! equivalent single dive of the repetitive dive
+    dive_plans(&self.logged_dives()).into_iter().flatten().collect()
+  }
+
+  pub fn evaluate(&self) -> Vec<DiveEvaluation> {
+    //! returns the table results of every dive of the day
+    let dives = self.logged_dives();
+    let plans = dive_plans(&dives);
+
+    annotate(&dives).into_iter().zip(self.dives.iter()).enumerate()
+      .map(|(index, (annotation, dive))| {
+        let previous = index.checked_sub(1).and_then(|previous| plans[previous]);
+        let equivalent_dive = annotation.equivalent_dive;
+        let deco = if annotation.within_no_decompression {
+          None
+        } else {
+          Some(equivalent_dive.advised_deco_dive())
+        };
+
+        DiveEvaluation {
+          dive: *dive,
+          equivalent_dive,
+          repet_letter: previous.map(|plan| plan.advised_repet_letter()),
+          residual_nitrogen_time: previous.map(|plan| plan.advised_residual_nitrogen_time()),
+          no_decompression_limit: dive.no_decompression_limit(),
+          group_letter: equivalent_dive.advised_group_letter(),
+          deco,
+        }
+      })
+      .collect()
+  }
+}
+
+/// splits a statement into its words along with their columns, starting
+/// at 1, within the line
+fn words(text: &str, offset: usize) -> Vec<(&str, usize)> {
+  let mut words = vec![];
+  let mut start: Option<(usize, usize)> = None;
+
+  for (position, (byte, c)) in text.char_indices().enumerate() {
+    match (c.is_whitespace(), start) {
+      (true, Some((from, column))) => {
+        words.push((&text[from..byte], column));
+        start = None;
+      }
+      (false, None) => start = Some((byte, offset + position + 1)),
+      _ => {}
+    }
+  }
+  if let Some((from, column)) = start {
+    words.push((&text[from..], column));
+  }
+
+  words
+}
+
+/// returns the feet of sea water of a depth written as 60fsw, 60ft or 18m,
+/// rounded up to the next foot
+fn depth(word: &str) -> Option<u16> {
+  let split = word.find(|c: char| !(c.is_ascii_digit() || c == '.'))?;
+  let value: f64 = word[..split].parse().ok().filter(|value: &f64| value.is_finite())?;
+  let feet = match &word[split..] {
+    "fsw" | "ft" => value,
+    "m" | "msw" => meters_to_feet(value),
+    _ => return None,
+  };
+  if feet > f64::from(u16::MAX) {
+    return None;
+  }
+  Some(fsw(feet))
+}
+
+/// returns the minutes of a duration written as 40min, 2h, 1h30 or 1h30min
+fn duration(word: &str) -> Option<u16> {
+  let (word, in_minutes) = match word.strip_suffix("min") {
+    Some(word) => (word, true),
+    None => (word, false),
+  };
+  let (hours, minutes) = match word.split_once('h') {
+    Some((hours, "")) if !in_minutes => (hours, "0"),
+    Some((hours, minutes)) if minutes.len() == 2 => (hours, minutes),
+    None if in_minutes => ("0", word),
+    _ => return None,
+  };
+
+  let hours: u16 = hours.parse().ok()?;
+  let minutes: u16 = minutes.parse().ok()?;
+  if hours > 0 && minutes >= 60 {
+    return None;
+  }
+  hours.checked_mul(60)?.checked_add(minutes)
+}
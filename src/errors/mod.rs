@@ -0,0 +1,202 @@
+use std::fmt;
+
+/// errors returned by the calculations of the diving-decompression crate
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DiveError {
+  /// the requested stop depth is not part of the decompression schedule
+  StopNotInSchedule {
+    /// the requested stop depth expressed in feet of sea water
+    depth: u16,
+  },
+  /// the time reported at a stop is longer than the scheduled stop time
+  StopTimeExceeded {
+    /// the stop depth expressed in feet of sea water
+    depth: u16,
+    /// the reported time at the stop expressed in minutes
+    time: u16,
+    /// the scheduled time at the stop expressed in minutes
+    scheduled: u16,
+  },
+  /// the oxygen fraction of the breathing mix is outside the accepted range
+  InvalidOxygenFraction {
+    /// the fraction of oxygen in the breathing mix
+    fo2: f64,
+  },
+  /// the depth of the dive exceeds the maximum operating depth of the mix
+  MaximumOperatingDepthExceeded {
+    /// the depth of the dive expressed in feet of sea water
+    depth: u16,
+    /// the maximum operating depth of the mix expressed in feet of sea water
+    maximum_operating_depth: u16,
+  },
+  /// the treatment table does not allow the requested extensions
+  ExtensionNotAllowed {
+    /// short code of the treatment table
+    table: String,
+    /// depth of the requested extensions expressed in feet of sea water
+    depth: u16,
+    /// number of requested extensions
+    count: u8,
+    /// number of extensions allowed at the depth
+    max_count: u8,
+  },
+  /// the treatment table code is not one of the embedded treatment tables
+  UnknownTreatmentTable {
+    /// short code of the treatment table
+    code: String,
+  },
+  /// the dive profile has no sample below the surface
+  EmptyProfile,
+  /// a sample of the dive profile is older than the previous sample
+  UnorderedSamples {
+    /// position of the sample in the profile
+    index: usize,
+  },
+  /// the text is not a valid clock time
+  InvalidClockTime {
+    /// the text that failed to parse
+    text: String,
+  },
+  /// the text is not a valid calendar date
+  InvalidDate {
+    /// the text that failed to parse
+    text: String,
+  },
+  /// the clock times of a dive record are not in chronological order
+  UnorderedClockTimes,
+  /// a dive to export has no start date and time
+  MissingStartTime {
+    /// position of the dive in the export
+    index: usize,
+  },
+  /// the station event does not follow the status of the diver
+  UnexpectedEvent {
+    /// the event recorded
+    event: String,
+    /// the status of the diver when the event was recorded
+    status: String,
+  },
+  /// the planned depth of a diver is not a dive depth
+  InvalidDepth {
+    /// the depth expressed in feet of sea water
+    depth: u16,
+  },
+  /// a dive plan text could not be parsed
+  Syntax {
+    /// line where parsing failed, starting at 1
+    line: usize,
+    /// column where parsing failed, starting at 1
+    column: usize,
+    /// description of the problem
+    message: String,
+  },
+  /// a record of a batch could not be read
+  InvalidRecord {
+    /// line of the record in the input, starting at 1
+    line: usize,
+    /// description of the problem found in the record
+    message: String,
+  },
+  /// the dive log could not be read
+  InvalidLog {
+    /// description of the problem found in the log
+    message: String,
+  },
+  /// a request to the API could not be read or holds invalid values
+  InvalidRequest {
+    /// description of the problem found in the request
+    message: String,
+  },
+  /// the dive is deeper than the no-decompression table
+  OutsideNoDecompressionTable {
+    /// the depth of the dive expressed in feet of sea water
+    depth: u16,
+  },
+  /// the dive has no schedule in the air decompression table
+  OutsideDecompressionTable {
+    /// the depth of the dive expressed in feet of sea water
+    depth: u16,
+    /// the bottom time of the dive expressed in minutes
+    bottom_time: u16,
+  },
+  /// the dive plan is not covered by the repetitive dive tables
+  OutsideRepetitiveTables {
+    /// the repetitive group letter at the end of the first dive
+    group_letter: String,
+    /// the surface interval expressed in minutes
+    surface_interval_time: u16,
+  },
+}
+
+impl fmt::Display for DiveError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      DiveError::StopNotInSchedule { depth } => {
+        write!(f, "there is no {} fsw stop in the decompression schedule", depth)
+      }
+      DiveError::StopTimeExceeded { depth, time, scheduled } => {
+        write!(f, "{} minutes at the {} fsw stop exceeds the scheduled {} minutes", time, depth, scheduled)
+      }
+      DiveError::InvalidOxygenFraction { fo2 } => {
+        write!(f, "the oxygen fraction {} is outside the accepted range for nitrox mixes", fo2)
+      }
+      DiveError::MaximumOperatingDepthExceeded { depth, maximum_operating_depth } => {
+        write!(f, "{} fsw exceeds the maximum operating depth of the mix ({} fsw)", depth, maximum_operating_depth)
+      }
+      DiveError::ExtensionNotAllowed { table, depth, count, max_count } => {
+        write!(f, "{} allows {} extensions at {} fsw, {} were requested", table, max_count, depth, count)
+      }
+      DiveError::UnknownTreatmentTable { code } => {
+        write!(f, "{} is not a known treatment table", code)
+      }
+      DiveError::EmptyProfile => {
+        write!(f, "the dive profile has no sample below the surface")
+      }
+      DiveError::UnorderedSamples { index } => {
+        write!(f, "sample {} of the dive profile is older than the previous sample", index)
+      }
+      DiveError::InvalidClockTime { text } => {
+        write!(f, "'{}' is not a valid clock time", text)
+      }
+      DiveError::InvalidDate { text } => {
+        write!(f, "'{}' is not a valid date", text)
+      }
+      DiveError::UnorderedClockTimes => {
+        write!(f, "the clock times of the dive record are not in chronological order")
+      }
+      DiveError::MissingStartTime { index } => {
+        write!(f, "dive {} has no start date and time", index)
+      }
+      DiveError::UnexpectedEvent { event, status } => {
+        write!(f, "{} is not expected during the {}", event, status)
+      }
+      DiveError::InvalidDepth { depth } => {
+        write!(f, "{} fsw is not a valid dive depth", depth)
+      }
+      DiveError::Syntax { line, column, message } => {
+        write!(f, "{}:{}: {}", line, column, message)
+      }
+      DiveError::InvalidRecord { line, message } => {
+        write!(f, "line {}: {}", line, message)
+      }
+      DiveError::InvalidLog { message } => {
+        write!(f, "invalid dive log: {}", message)
+      }
+      DiveError::InvalidRequest { message } => {
+        write!(f, "invalid request: {}", message)
+      }
+      DiveError::OutsideNoDecompressionTable { depth } => {
+        write!(f, "{} fsw is deeper than the no-decompression table", depth)
+      }
+      DiveError::OutsideDecompressionTable { depth, bottom_time } => {
+        write!(f, "{} fsw for {} minutes has no schedule in the air decompression table", depth, bottom_time)
+      }
+      DiveError::OutsideRepetitiveTables { group_letter, surface_interval_time } => {
+        write!(f, "a {} minutes surface interval after group {} is outside the repetitive dive tables", surface_interval_time, group_letter)
+      }
+    }
+  }
+}
+
+impl std::error::Error for DiveError {}
@@ -0,0 +1,299 @@
+use std::fmt::Write;
+
+use crate::{Dive, DivePlan};
+use crate::advisories::{Advised, Advisory, AdvisoryCode};
+use crate::airtables::RowDeco;
+use crate::errors::DiveError;
+
+/// names of the input columns, in the order expected when the input has
+/// no header line
+pub const INPUT_COLUMNS: [&str; 4] = ["depth", "bottom_time", "surface_interval", "next_depth"];
+
+/// names of the result columns appended to every row of the output
+pub const RESULT_COLUMNS: [&str; 7] = [
+  "ndl",
+  "group_letter",
+  "repet_letter",
+  "rnt",
+  "deco_schedule",
+  "advisories",
+  "error",
+];
+
+/// a dive read from a row of the batch, the surface interval and the next
+/// dive depth are only given for repetitive dives
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BatchRow {
+  /// depth of the dive expressed in feet of sea water
+  pub depth: u16,
+  /// bottom time of the dive expressed in minutes
+  pub bottom_time: u16,
+  /// surface interval before the next dive expressed in minutes
+  pub surface_interval: Option<u16>,
+  /// depth of the next dive expressed in feet of sea water
+  pub next_dive_depth: Option<u16>,
+}
+
+/// the table results of a row of the batch
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BatchEvaluation {
+  /// no decompression limit at the depth expressed in minutes
+  pub no_decompression_limit: u16,
+  /// repetitive group letter at the end of the dive
+  pub group_letter: String,
+  /// repetitive group letter at the start of the next dive
+  pub repet_letter: Option<String>,
+  /// residual nitrogen time of the next dive expressed in minutes
+  pub residual_nitrogen_time: Option<u16>,
+  /// the air decompression schedule, when the bottom time exceeds the no
+  /// decompression limit
+  pub deco: Option<RowDeco>,
+  /// the advisories raised by the row, without duplicates
+  pub advisories: Vec<Advisory>,
+}
+
+/// the outcome of a row of the batch
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BatchResult {
+  /// line of the row in the input, starting at 1
+  pub line: usize,
+  /// the input fields of the row as read
+  pub fields: Vec<String>,
+  /// the table results of the row, or the reason the row was not evaluated
+  pub evaluation: Result<BatchEvaluation, DiveError>,
+}
+
+/// evaluate_batch() evaluates every row of the CSV input. the columns are
+/// the depth, the bottom time, the surface interval and the next dive
+/// depth, either in that order or in any order named by a header line.
+/// surface intervals are written in minutes or as H:MM. a row that can not
+/// be evaluated is reported with its error and the batch goes on
+pub fn evaluate_batch(input: &str) -> (Vec<String>, Vec<BatchResult>) {
+  let mut records = input.lines().enumerate()
+    .filter(|(_, line)| !line.trim().is_empty())
+    .map(|(index, line)| (index + 1, split_record(line)))
+    .peekable();
+
+  let header = match records.peek() {
+    Some((_, fields)) if fields.first().is_some_and(|field| field.trim().parse::<f64>().is_err()) => {
+      records.next().map(|(_, fields)| fields)
+    }
+    _ => None,
+  };
+  let columns = header.clone()
+    .unwrap_or_else(|| INPUT_COLUMNS.iter().map(|column| String::from(*column)).collect());
+
+  let results = records
+    .map(|(line, fields)| {
+      let evaluation = parse_row(line, &columns, &fields).map(evaluate_row);
+      BatchResult { line, fields, evaluation }
+    })
+    .collect();
+
+  (columns, results)
+}
+
+/// evaluate_csv() evaluates every row of the CSV input and returns the
+/// input columns followed by the RESULT_COLUMNS as CSV
+pub fn evaluate_csv(input: &str) -> String {
+  let (columns, results) = evaluate_batch(input);
+  write_results(&columns, &results)
+}
+
+/// write_results() returns the batch results as CSV: a header line, then
+/// every row with its input fields followed by the RESULT_COLUMNS
+pub fn write_results(columns: &[String], results: &[BatchResult]) -> String {
+  let mut csv = String::new();
+  let header: Vec<String> = columns.iter().cloned()
+    .chain(RESULT_COLUMNS.iter().map(|column| String::from(*column)))
+    .collect();
+  write_record(&mut csv, &header);
+
+  for result in results.iter() {
+    let mut record: Vec<String> = result.fields.clone();
+    record.resize(columns.len(), String::new());
+
+    match &result.evaluation {
+      Ok(evaluation) => {
+        record.push(evaluation.no_decompression_limit.to_string());
+        record.push(evaluation.group_letter.clone());
+        record.push(evaluation.repet_letter.clone().unwrap_or_default());
+        record.push(evaluation.residual_nitrogen_time.map_or_else(String::new, |rnt| rnt.to_string()));
+        record.push(evaluation.deco.as_ref().map_or_else(|| String::from("NO-D"), deco_schedule));
+        record.push(evaluation.advisories.iter().map(|advisory| code_name(advisory.code)).collect::<Vec<_>>().join(";"));
+        record.push(String::new());
+      }
+      Err(error) => {
+        record.extend(std::iter::repeat_n(String::new(), RESULT_COLUMNS.len() - 1));
+        record.push(error.to_string());
+      }
+    }
+    write_record(&mut csv, &record);
+  }
+
+  csv
+}
+
+fn parse_row(line: usize, columns: &[String], fields: &[String]) -> Result<BatchRow, DiveError> {
+  if fields.len() != columns.len() {
+    return Err(DiveError::InvalidRecord {
+      line,
+      message: format!("expected {} fields, found {}", columns.len(), fields.len()),
+    });
+  }
+
+  let field = |name: &str| -> Option<&str> {
+    columns.iter()
+      .position(|column| column.trim().eq_ignore_ascii_case(name))
+      .map(|position| fields[position].trim())
+      .filter(|text| !text.is_empty())
+  };
+  let number = |name: &str| -> Result<Option<u16>, DiveError> {
+    field(name)
+      .map(|text| text.parse::<u16>().map_err(|_| DiveError::InvalidRecord {
+        line,
+        message: format!("'{}' is not a valid {}", text, name),
+      }))
+      .transpose()
+  };
+  let required = |name: &str| -> Result<u16, DiveError> {
+    number(name)?.ok_or_else(|| DiveError::InvalidRecord { line, message: format!("the {} is missing", name) })
+  };
+
+  let surface_interval = field("surface_interval")
+    .map(|text| interval(text).ok_or_else(|| DiveError::InvalidRecord {
+      line,
+      message: format!("'{}' is not a valid surface_interval", text),
+    }))
+    .transpose()?;
+  let next_dive_depth = number("next_depth")?;
+  if surface_interval.is_some() != next_dive_depth.is_some() {
+    return Err(DiveError::InvalidRecord {
+      line,
+      message: String::from("the surface_interval and the next_depth go together"),
+    });
+  }
+
+  Ok(BatchRow {
+    depth: required("depth")?,
+    bottom_time: required("bottom_time")?,
+    surface_interval,
+    next_dive_depth,
+  })
+}
+
+fn evaluate_row(row: BatchRow) -> BatchEvaluation {
+  let dive = Dive::new(row.depth, row.bottom_time);
+  let mut advisories: Vec<Advisory> = vec![];
+  let mut collect = |advised: &[Advisory]| {
+    for advisory in advised.iter() {
+      if !advisories.iter().any(|collected| collected.code == advisory.code) {
+        advisories.push(advisory.clone());
+      }
+    }
+  };
+
+  let group: Advised<String> = dive.advised_group_letter();
+  collect(&group.advisories);
+  let (group_letter, deco) = if dive.requires_decompression() {
+    let deco = dive.advised_deco_dive();
+    collect(&deco.advisories);
+    (deco.value.repetgroup_letter.clone(), Some(deco.value))
+  } else {
+    (group.value, None)
+  };
+
+  let plan = match (row.surface_interval, row.next_dive_depth) {
+    (Some(surface_interval), Some(next_dive_depth)) => {
+      Some(DivePlan::new(row.depth, row.bottom_time, surface_interval, next_dive_depth))
+    }
+    _ => None,
+  };
+  let repet_letter = plan.map(|plan| {
+    let repet = plan.advised_repet_letter();
+    collect(&repet.advisories);
+    repet.value
+  });
+  let residual_nitrogen_time = plan.map(|plan| {
+    let rnt = plan.advised_residual_nitrogen_time();
+    collect(&rnt.advisories);
+    rnt.value
+  });
+
+  BatchEvaluation {
+    no_decompression_limit: dive.no_decompression_limit(),
+    group_letter,
+    repet_letter,
+    residual_nitrogen_time,
+    deco,
+    advisories,
+  }
+}
+
+/// returns the minutes of a surface interval written in minutes or as H:MM
+fn interval(text: &str) -> Option<u16> {
+  match text.split_once(':') {
+    Some((hours, minutes)) if minutes.len() == 2 => {
+      let hours: u16 = hours.parse().ok()?;
+      let minutes: u16 = minutes.parse().ok().filter(|minutes| *minutes < 60)?;
+      hours.checked_mul(60)?.checked_add(minutes)
+    }
+    Some(_) => None,
+    None => text.parse().ok(),
+  }
+}
+
+/// returns the air decompression stops deepest first written as depth/time
+fn deco_schedule(deco: &RowDeco) -> String {
+  let mut stops = deco.air_deco_stops.clone();
+  stops.sort_by_key(|stop| std::cmp::Reverse(stop.depth));
+  stops.iter()
+    .map(|stop| format!("{}/{}", stop.depth, stop.time))
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+/// returns the snake case name of the advisory code
+fn code_name(code: AdvisoryCode) -> String {
+  serde_json::to_value(code).ok()
+    .and_then(|value| value.as_str().map(String::from))
+    .unwrap_or_default()
+}
+
+/// splits a CSV line into its fields, honoring double quoted fields
+fn split_record(line: &str) -> Vec<String> {
+  let mut fields = vec![];
+  let mut field = String::new();
+  let mut quoted = false;
+  let mut chars = line.chars().peekable();
+
+  while let Some(c) = chars.next() {
+    match c {
+      '"' if quoted && chars.peek() == Some(&'"') => {
+        field.push('"');
+        chars.next();
+      }
+      '"' => quoted = !quoted,
+      ',' if !quoted => fields.push(std::mem::take(&mut field)),
+      c => field.push(c),
+    }
+  }
+  fields.push(field);
+
+  fields
+}
+
+/// appends the fields to the CSV as a line, quoting them when needed
+fn write_record(csv: &mut String, fields: &[String]) {
+  let line = fields.iter()
+    .map(|field| {
+      if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+      } else {
+        field.clone()
+      }
+    })
+    .collect::<Vec<_>>()
+    .join(",");
+  let _ = writeln!(csv, "{}", line);
+}
@@ -0,0 +1,97 @@
+use std::fmt::Write;
+
+use crate::errors::DiveError;
+use crate::formats::{annotate, LoggedDive};
+use crate::nitrox::NitroxDive;
+use crate::timekeeping::DateTime;
+
+/// the table name written in the ZAR block of the DL7 files
+pub const DL7_TABLES: &str = "USN-AIR-REV7";
+
+/// export() writes a dive, or a day of repetitive dives in time order, as
+/// DAN DL7 segments. depths are written in feet of sea water and every dive
+/// is breathed on the mix with the given fraction of oxygen. `exported` is
+/// the date and time written in the FSH header.
+///
+/// the ZAR block holds one line per dive with the table results: the
+/// tables, the dive number, the table depth and bottom time (the equivalent
+/// air depth for nitrox and the residual nitrogen time added for repetitive
+/// dives), the schedule used as table row depth and bottom time, the group letter at the end of the dive, the
+/// group letter at the start of the next dive and its residual nitrogen time
+pub fn export(dives: &[LoggedDive], fo2: f64, exported: DateTime) -> Result<String, DiveError> {
+  let air = (fo2 - 0.21).abs() < 1e-9;
+  let gas = if air { String::from("AIR") } else { format!("EAN{:.0}", fo2 * 100.0) };
+
+  // nitrox tables are entered at the equivalent air depth of the mix
+  let mut table_dives = dives.to_vec();
+  if !air {
+    for table_dive in table_dives.iter_mut() {
+      let dive = table_dive.dive();
+      table_dive.reduction.dive = NitroxDive::new(dive.depth, dive.bottom_time, fo2).plan()?.air_dive;
+    }
+  }
+  let annotations = annotate(&table_dives);
+
+  let mut dl7 = String::new();
+  let _ = writeln!(dl7, "FSH|^~<>{{}}|{}^{}|ZXU|{}|", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"), timestamp(exported));
+  dl7.push_str("ZRH|^~<>{}|||FSWG|ThFt|F|PSIA|CF|\n");
+
+  dl7.push_str("ZAR{\n");
+  for (index, annotation) in annotations.iter().enumerate() {
+    let schedule = if annotation.within_no_decompression {
+      String::from("NO-D")
+    } else {
+      let row = annotation.equivalent_dive.deco_dive();
+      format!("{}/{}", annotation.equivalent_dive.deco_table_depth(), row.max_time)
+    };
+    let _ = writeln!(
+      dl7,
+      "{}|{}|{}|{}|{}|{}|{}|{}|",
+      DL7_TABLES,
+      index + 1,
+      annotation.equivalent_dive.depth,
+      annotation.equivalent_dive.bottom_time,
+      schedule,
+      annotation.end_group,
+      annotation.next_group.as_deref().unwrap_or(""),
+      annotation.residual_nitrogen_time.map_or_else(String::new, |rnt| rnt.to_string()),
+    );
+  }
+  dl7.push_str("ZAR}\n");
+
+  for (index, dive) in dives.iter().enumerate() {
+    let start = dive.start.ok_or(DiveError::MissingStartTime { index })?;
+    let samples = &dive.profile.samples;
+
+    let _ = writeln!(dl7, "ZDH|{}|{}|I|Q1S|{}||||", index + 1, dive.id.as_deref().unwrap_or(""), timestamp(start));
+    dl7.push_str("ZDP{\n");
+    for (position, sample) in samples.iter().enumerate() {
+      let po2 = fo2 * (sample.depth + 33.0) / 33.0;
+      let switch = if position == 0 { gas.as_str() } else { "" };
+      let _ = writeln!(dl7, "|{:.2}|{:.1}|{}|{:.2}|", f64::from(sample.time) / 60.0, sample.depth, switch, po2);
+    }
+    dl7.push_str("ZDP}\n");
+
+    let end = dive.reduction.reach_surface
+      .or_else(|| samples.last().map(|sample| sample.time))
+      .unwrap_or(0);
+    let max_depth = samples.iter().map(|sample| sample.depth).fold(0.0, f64::max);
+    let end = DateTime::from_seconds(start.seconds() + i64::from(end));
+    let _ = writeln!(dl7, "ZDT|{}|{}|{:.1}|{}||", index + 1, dive.id.as_deref().unwrap_or(""), max_depth, timestamp(end));
+  }
+
+  Ok(dl7)
+}
+
+/// returns the date and time written as YYYYMMDDHHMMSS
+fn timestamp(date_time: DateTime) -> String {
+  format!(
+    "{:04}{:02}{:02}{:02}{:02}{:02}",
+    date_time.date.year,
+    date_time.date.month,
+    date_time.date.day,
+    date_time.time.hour,
+    date_time.time.minute,
+    date_time.time.second,
+  )
+}
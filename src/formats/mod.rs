@@ -0,0 +1,180 @@
+use crate::{Dive, DivePlan};
+use crate::advisories::Advisory;
+use crate::airtables::RowDeco;
+use crate::profile::{DiveProfile, ProfileReduction};
+use crate::timekeeping::DateTime;
+
+/// this module provides the batch evaluation of CSV dive lists
+pub mod csv;
+/// this module provides the export of DAN DL7 dive reports
+pub mod dl7;
+/// this module provides the import of Subsurface logbooks
+pub mod subsurface;
+/// this module provides the import and export of UDDF dive logs
+pub mod uddf;
+
+/// a dive read from a dive log
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LoggedDive {
+  /// identifier of the dive within the log, if any
+  pub id: Option<String>,
+  /// date and time the diver left the surface, if logged
+  pub start: Option<DateTime>,
+  /// surface interval before the dive expressed in seconds, none for the
+  /// first dive of a series
+  pub surface_interval: Option<u32>,
+  /// the logged depth profile expressed in feet of sea water
+  pub profile: DiveProfile,
+  /// the table inputs reduced from the profile
+  pub reduction: ProfileReduction,
+}
+
+impl LoggedDive {
+  pub fn dive(&self) -> Dive {
+    //! returns the table dive reduced from the logged profile
+    self.reduction.dive
+  }
+}
+
+/// a dive planned on the tables, ready to be exported
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PlannedDive {
+  /// the planned table dive
+  pub dive: Dive,
+  /// the air decompression schedule of the dive, none when the dive stays
+  /// within the no decompression limit
+  pub schedule: Option<RowDeco>,
+  /// surface interval before the dive expressed in seconds, none for the
+  /// first dive of a series
+  pub surface_interval: Option<u32>,
+}
+
+impl PlannedDive {
+  pub fn new(dive: Dive) -> Self {
+    //! Instantiates a new PlannedDive object decompressed on the air
+    //! schedule when the bottom time exceeds the no decompression limit
+    let schedule = if dive.requires_decompression() {
+      Some(dive.deco_dive())
+    } else {
+      None
+    };
+
+    Self {
+      dive,
+      schedule,
+      surface_interval: None,
+    }
+  }
+
+  pub fn with_surface_interval(self, surface_interval: u32) -> Self {
+    //! returns the PlannedDive object following the previous dive after
+    //! the surface interval expressed in seconds
+    Self {
+      surface_interval: Some(surface_interval),
+      ..self
+    }
+  }
+
+  pub fn profile(&self) -> DiveProfile {
+    //! returns the planned depth profile of the dive
+    let stops = self.schedule.as_ref().map_or(&[][..], |schedule| &schedule.air_deco_stops[..]);
+    DiveProfile::planned(self.dive, stops)
+  }
+
+  pub fn logged(&self) -> LoggedDive {
+    //! returns the LoggedDive object of the dive made as planned, the
+    //! table dive being the planned dive itself
+    let profile = self.profile();
+    let reduction = ProfileReduction {
+      max_depth: self.dive.depth,
+      leave_surface: 0,
+      leave_bottom: u32::from(self.dive.bottom_time) * 60,
+      reach_surface: profile.samples.last().map(|sample| sample.time),
+      bottom_time: self.dive.bottom_time,
+      dive: self.dive,
+    };
+
+    LoggedDive {
+      id: None,
+      start: None,
+      surface_interval: self.surface_interval,
+      profile,
+      reduction,
+    }
+  }
+}
+
+/// dive_plans() returns the DivePlan chain of consecutive logged dives: one
+/// entry for every dive followed by another, none when the next dive has
+/// no surface interval and starts a new series. the first dive of a plan
+/// following another plan is the equivalent single dive of the repetitive
+/// dive, so that residual nitrogen carries along the chain, made under the
+/// exposure of the repetitive dive. surface intervals are rounded down to
+/// the minute
+pub fn dive_plans(dives: &[LoggedDive]) -> Vec<Option<DivePlan>> {
+  let mut plans: Vec<Option<DivePlan>> = vec![];
+
+  for pair in dives.windows(2) {
+    let (dive, next) = (&pair[0], &pair[1]);
+    let plan = next.surface_interval.map(|surface_interval| {
+      let first_dive = match plans.last() {
+        Some(Some(previous)) => previous.repetitive_dive(dive.dive().bottom_time).with_exposure(dive.dive().exposure),
+        _ => dive.dive(),
+      };
+      DivePlan::from_dive(first_dive, (surface_interval / 60) as u16, next.dive().depth)
+    });
+    plans.push(plan);
+  }
+
+  plans
+}
+
+/// the repetitive diving status of a logged dive
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct GroupAnnotation {
+  /// the single dive the logged dive counts as on the tables: the logged
+  /// dive itself, or for a repetitive dive the residual nitrogen time
+  /// added to its bottom time
+  pub equivalent_dive: Dive,
+  /// true when the equivalent dive stays within the no decompression limit
+  pub within_no_decompression: bool,
+  /// repetitive group letter at the end of the dive
+  pub end_group: String,
+  /// repetitive group letter at the start of the next dive, none when the
+  /// next dive starts a new series
+  pub next_group: Option<String>,
+  /// residual nitrogen time of the next dive expressed in minutes, none
+  /// when the next dive starts a new series
+  pub residual_nitrogen_time: Option<u16>,
+  /// the advisories of the repetitive group and residual nitrogen time of
+  /// the next dive, such as a plan outside the repetitive dive tables
+  #[serde(default)]
+  pub advisories: Vec<Advisory>,
+}
+
+/// annotate() returns the repetitive diving status of every logged dive,
+/// walking the dives in the given order along the DivePlan chain
+pub fn annotate(dives: &[LoggedDive]) -> Vec<GroupAnnotation> {
+  let plans = dive_plans(dives);
+
+  dives.iter().enumerate()
+    .map(|(index, dive)| {
+      let previous = index.checked_sub(1).and_then(|previous| plans[previous]);
+      let equivalent_dive = previous.map_or_else(
+        || dive.dive(),
+        |plan| plan.repetitive_dive(dive.dive().bottom_time).with_exposure(dive.dive().exposure),
+      );
+      let next = plans.get(index).copied().flatten();
+      let residual_nitrogen_time = next.map(|plan| plan.advised_residual_nitrogen_time());
+
+      GroupAnnotation {
+        equivalent_dive,
+        within_no_decompression: !equivalent_dive.requires_decompression(),
+        end_group: equivalent_dive.end_group_letter(),
+        next_group: next.map(|plan| plan.repet_letter()),
+        residual_nitrogen_time: residual_nitrogen_time.as_ref().map(|rnt| rnt.value),
+        advisories: residual_nitrogen_time.map_or_else(Vec::new, |rnt| rnt.advisories),
+      }
+    })
+    .collect()
+}
@@ -0,0 +1,160 @@
+use roxmltree::{Document, Node};
+
+use crate::errors::DiveError;
+use crate::formats::{annotate, GroupAnnotation, LoggedDive};
+use crate::profile::{DiveProfile, ProfileSample};
+use crate::timekeeping::DateTime;
+use crate::treatment::tender::REPETITIVE_WINDOW;
+use crate::units::meters_to_feet;
+
+/// a dive of a Subsurface logbook along with its repetitive diving status
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AnnotatedDive {
+  /// the logged dive
+  pub dive: LoggedDive,
+  /// the repetitive diving status of the dive
+  pub annotation: GroupAnnotation,
+}
+
+/// import() reads the dives of a Subsurface logbook in time order. the
+/// samples of the first dive computer make the profile, a dive logged
+/// without samples is taken as a square profile at its maximum depth for
+/// its whole duration. the surface interval before a dive is measured from
+/// the end of the previous dive, dives more than REPETITIVE_WINDOW minutes
+/// apart start a new series
+pub fn import(xml: &str) -> Result<Vec<LoggedDive>, DiveError> {
+  let document = Document::parse(xml)
+    .map_err(|error| DiveError::InvalidLog { message: error.to_string() })?;
+  let root = document.root_element();
+  if root.tag_name().name() != "divelog" {
+    return Err(DiveError::InvalidLog { message: format!("expected a subsurface divelog, found <{}>", root.tag_name().name()) });
+  }
+
+  let mut dives = root.descendants()
+    .filter(|node| node.has_tag_name("dive"))
+    .map(import_dive)
+    .collect::<Result<Vec<_>, DiveError>>()?;
+  dives.sort_by_key(|dive| dive.start);
+
+  let mut previous_end: Option<i64> = None;
+  for dive in dives.iter_mut() {
+    let start = dive.start.map(DateTime::seconds);
+    if let (Some(start), Some(end)) = (start, previous_end) {
+      if start < end {
+        return Err(DiveError::InvalidLog {
+          message: format!("dive {} starts before the previous dive ends", dive.id.as_deref().unwrap_or("?")),
+        });
+      }
+      let surface_interval = start - end;
+      if surface_interval < i64::from(REPETITIVE_WINDOW) * 60 {
+        dive.surface_interval = Some(surface_interval as u32);
+      }
+    }
+
+    let duration = dive.reduction.reach_surface
+      .or_else(|| dive.profile.samples.last().map(|sample| sample.time))
+      .unwrap_or(0);
+    previous_end = start.map(|start| start + i64::from(duration));
+  }
+
+  Ok(dives)
+}
+
+/// annotated() reads the dives of a Subsurface logbook in time order and
+/// annotates each with its repetitive diving status
+pub fn annotated(xml: &str) -> Result<Vec<AnnotatedDive>, DiveError> {
+  let dives = import(xml)?;
+  let annotations = annotate(&dives);
+
+  Ok(dives.into_iter()
+    .zip(annotations)
+    .map(|(dive, annotation)| AnnotatedDive { dive, annotation })
+    .collect())
+}
+
+fn import_dive(dive: Node) -> Result<LoggedDive, DiveError> {
+  let start = match (dive.attribute("date"), dive.attribute("time")) {
+    (Some(date), Some(time)) => Some(format!("{} {}", date, time).parse::<DateTime>()?),
+    _ => None,
+  };
+
+  let computer = dive.children().find(|node| node.has_tag_name("divecomputer")).unwrap_or(dive);
+  let mut samples = vec![ProfileSample { time: 0, depth: 0.0 }];
+  let mut depth = 0.0;
+  for sample in computer.children().filter(|node| node.has_tag_name("sample")) {
+    let time = match sample.attribute("time") {
+      Some(time) => duration(time)?,
+      None => continue,
+    };
+    // subsurface leaves out the depth of a sample when it did not change
+    if let Some(text) = sample.attribute("depth") {
+      depth = length(text)?;
+    }
+    if time == 0 {
+      samples[0].depth = depth;
+    } else {
+      samples.push(ProfileSample { time, depth });
+    }
+  }
+
+  if samples.len() == 1 {
+    let max_depth = computer.children()
+      .chain(dive.children())
+      .find(|node| node.has_tag_name("depth") && node.attribute("max").is_some())
+      .and_then(|node| node.attribute("max"))
+      .map(length)
+      .transpose()?;
+    let time = dive.attribute("duration").map(duration).transpose()?;
+    if let (Some(max_depth), Some(time)) = (max_depth, time) {
+      samples = vec![
+        ProfileSample { time: 0, depth: 0.0 },
+        ProfileSample { time: 0, depth: max_depth },
+        ProfileSample { time, depth: max_depth },
+        ProfileSample { time, depth: 0.0 },
+      ];
+    }
+  }
+
+  let profile = DiveProfile::from_samples(samples)?;
+  let reduction = profile.reduce()?;
+
+  Ok(LoggedDive {
+    id: dive.attribute("number").map(String::from),
+    start,
+    surface_interval: None,
+    profile,
+    reduction,
+  })
+}
+
+/// returns the seconds of a duration written as MM:SS min or HH:MM:SS
+fn duration(text: &str) -> Result<u32, DiveError> {
+  let invalid = || DiveError::InvalidLog { message: format!("'{}' is not a valid duration", text) };
+  let fields = text.trim().trim_end_matches("min").trim()
+    .split(':')
+    .map(|field| field.parse::<u32>().map_err(|_| invalid()))
+    .collect::<Result<Vec<_>, DiveError>>()?;
+
+  match fields[..] {
+    [minutes] => Ok(minutes * 60),
+    [minutes, seconds] if seconds < 60 => Ok(minutes * 60 + seconds),
+    [hours, minutes, seconds] if minutes < 60 && seconds < 60 => Ok(hours * 3600 + minutes * 60 + seconds),
+    _ => Err(invalid()),
+  }
+}
+
+/// returns the feet of sea water of a depth written in m or ft
+fn length(text: &str) -> Result<f64, DiveError> {
+  let invalid = || DiveError::InvalidLog { message: format!("'{}' is not a valid depth", text) };
+  let text = text.trim();
+  let (value, feet) = match text.strip_suffix("ft") {
+    Some(value) => (value, true),
+    None => (text.strip_suffix('m').unwrap_or(text), false),
+  };
+
+  let value = value.trim().parse::<f64>()
+    .ok()
+    .filter(|value| value.is_finite() && *value >= 0.0)
+    .ok_or_else(invalid)?;
+  Ok(if feet { value } else { meters_to_feet(value) })
+}
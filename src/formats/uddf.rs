@@ -0,0 +1,159 @@
+use std::fmt::Write;
+
+use roxmltree::{Document, Node};
+
+use crate::errors::DiveError;
+use crate::formats::{LoggedDive, PlannedDive};
+use crate::profile::{DiveProfile, ProfileSample};
+use crate::units::{feet_to_meters, meters_to_feet};
+
+/// namespace of the UDDF documents written by export()
+pub const UDDF_NAMESPACE: &str = "http://www.streit.cc/uddf/3.2/";
+
+/// version of the UDDF documents written by export()
+pub const UDDF_VERSION: &str = "3.2.0";
+
+/// import() reads the dives of a UDDF document in document order. depths
+/// are logged in meters and converted to feet of sea water, times are
+/// logged in seconds. a dive without a surface interval, or with an
+/// infinite one, starts a new series of dives
+pub fn import(xml: &str) -> Result<Vec<LoggedDive>, DiveError> {
+  let document = Document::parse(xml)
+    .map_err(|error| DiveError::InvalidLog { message: error.to_string() })?;
+  let root = document.root_element();
+  if root.tag_name().name() != "uddf" {
+    return Err(DiveError::InvalidLog { message: format!("expected a uddf document, found <{}>", root.tag_name().name()) });
+  }
+
+  root.descendants()
+    .filter(|node| node.has_tag_name("dive"))
+    .map(import_dive)
+    .collect()
+}
+
+/// export() writes the planned dives as a UDDF document. every dive holds
+/// the waypoints of its planned profile with depths in meters and times in
+/// seconds, and every stop waypoint carries its mandatory decompression
+/// stop. a dive without a surface interval starts a new repetition group
+pub fn export(dives: &[PlannedDive]) -> String {
+  let mut xml = String::new();
+  xml.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+  let _ = writeln!(xml, "<uddf xmlns=\"{}\" version=\"{}\">", UDDF_NAMESPACE, UDDF_VERSION);
+  xml.push_str("  <generator>\n");
+  let _ = writeln!(xml, "    <name>{}</name>", env!("CARGO_PKG_NAME"));
+  let _ = writeln!(xml, "    <version>{}</version>", env!("CARGO_PKG_VERSION"));
+  xml.push_str("  </generator>\n");
+  xml.push_str("  <profiledata>\n");
+
+  let mut groups = 0;
+  for (index, planned) in dives.iter().enumerate() {
+    if index == 0 || planned.surface_interval.is_none() {
+      if index > 0 {
+        xml.push_str("    </repetitiongroup>\n");
+      }
+      groups += 1;
+      let _ = writeln!(xml, "    <repetitiongroup id=\"rg{}\">", groups);
+    }
+    export_dive(&mut xml, index + 1, planned);
+  }
+  if groups > 0 {
+    xml.push_str("    </repetitiongroup>\n");
+  }
+
+  xml.push_str("  </profiledata>\n");
+  xml.push_str("</uddf>\n");
+  xml
+}
+
+fn export_dive(xml: &mut String, number: usize, planned: &PlannedDive) {
+  let profile = planned.profile();
+  let stops = planned.schedule.as_ref().map_or(&[][..], |schedule| &schedule.air_deco_stops[..]);
+
+  let _ = writeln!(xml, "      <dive id=\"dive{}\">", number);
+  xml.push_str("        <informationbeforedive>\n");
+  let _ = writeln!(xml, "          <divenumber>{}</divenumber>", number);
+  match planned.surface_interval {
+    Some(seconds) => {
+      let _ = writeln!(xml, "          <surfaceintervalbeforedive><passedtime>{}</passedtime></surfaceintervalbeforedive>", seconds);
+    }
+    None => xml.push_str("          <surfaceintervalbeforedive><infinity/></surfaceintervalbeforedive>\n"),
+  }
+  xml.push_str("        </informationbeforedive>\n");
+
+  xml.push_str("        <samples>\n");
+  for (index, sample) in profile.samples.iter().enumerate() {
+    let _ = write!(xml, "          <waypoint><depth>{:.3}</depth><divetime>{}</divetime>", feet_to_meters(sample.depth), sample.time);
+    let arriving = profile.samples.get(index + 1).is_some_and(|next| next.depth == sample.depth && next.time > sample.time);
+    if let Some(stop) = stops.iter().find(|stop| arriving && f64::from(stop.depth) == sample.depth && sample.time > 0) {
+      let _ = write!(
+        xml,
+        "<decostop kind=\"mandatory\" decodepth=\"{:.3}\" duration=\"{}\"/>",
+        feet_to_meters(f64::from(stop.depth)),
+        u32::from(stop.time) * 60,
+      );
+    }
+    xml.push_str("</waypoint>\n");
+  }
+  xml.push_str("        </samples>\n");
+
+  let runtime = profile.samples.last().map_or(0, |sample| sample.time);
+  xml.push_str("        <informationafterdive>\n");
+  let _ = writeln!(xml, "          <greatestdepth>{:.3}</greatestdepth>", feet_to_meters(f64::from(planned.dive.depth)));
+  let _ = writeln!(xml, "          <diveduration>{}</diveduration>", runtime);
+  xml.push_str("        </informationafterdive>\n");
+  xml.push_str("      </dive>\n");
+}
+
+fn import_dive(dive: Node) -> Result<LoggedDive, DiveError> {
+  let surface_interval = child(dive, "informationbeforedive")
+    .and_then(|info| child(info, "surfaceintervalbeforedive"))
+    .and_then(|interval| child(interval, "passedtime"))
+    .map(number)
+    .transpose()?
+    .map(|seconds| seconds as u32);
+
+  let samples = child(dive, "samples")
+    .map_or_else(Vec::new, |samples| samples.children().filter(|node| node.has_tag_name("waypoint")).collect());
+  let samples = samples.into_iter()
+    .map(|waypoint| {
+      let depth = number(required(waypoint, "depth")?)?;
+      let time = number(required(waypoint, "divetime")?)?;
+      Ok(ProfileSample { time: time as u32, depth: meters_to_feet(depth) })
+    })
+    .collect::<Result<Vec<_>, DiveError>>()?;
+
+  let profile = DiveProfile::from_samples(samples)?;
+  let reduction = profile.reduce()?;
+
+  Ok(LoggedDive {
+    id: dive.attribute("id").map(String::from),
+    start: None,
+    surface_interval,
+    profile,
+    reduction,
+  })
+}
+
+/// returns the first child element with the given name
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+  node.children().find(|child| child.has_tag_name(name))
+}
+
+/// returns the first child element with the given name or an error naming
+/// the missing element
+fn required<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Result<Node<'a, 'input>, DiveError> {
+  child(node, name).ok_or_else(|| DiveError::InvalidLog {
+    message: format!("<{}> has no <{}> element", node.tag_name().name(), name),
+  })
+}
+
+/// returns the number held by the text of the element
+fn number(node: Node) -> Result<f64, DiveError> {
+  let text = node.text().unwrap_or("").trim();
+  text.parse::<f64>()
+    .ok()
+    .filter(|value| value.is_finite() && *value >= 0.0)
+    .ok_or_else(|| DiveError::InvalidLog {
+      message: format!("<{}> holds '{}' instead of a number", node.tag_name().name(), text),
+    })
+}
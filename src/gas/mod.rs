@@ -0,0 +1,200 @@
+use crate::Dive;
+use crate::advisories::{Advisory, AdvisoryCode};
+use crate::airtables::DecoStops;
+
+/// this module provides the oxygen quantity planning of decompression
+pub mod oxygen_supply;
+/// this module provides the air supply check of surface-supplied dives
+pub mod surface_supplied;
+
+/// ascent rate to the first stop and between stops expressed in feet of
+/// sea water per minute
+pub const ASCENT_RATE: f64 = 30.0;
+
+/// descent rate from the surface to the bottom expressed in feet of sea
+/// water per minute
+pub const DESCENT_RATE: f64 = 60.0;
+
+/// a scuba cylinder and its current fill
+#[derive(Serialize, Deserialize, Copy, Clone, Debug)]
+pub struct Cylinder {
+  /// gas capacity at the rated pressure expressed in standard cubic feet
+  pub capacity: f64,
+  /// rated pressure of the cylinder expressed in psi
+  pub rated_pressure: f64,
+  /// current pressure of the cylinder expressed in psi
+  pub pressure: f64,
+}
+
+/// the gas that must remain in the cylinder on surfacing
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
+#[serde(tag = "rule", content = "psi", rename_all = "snake_case")]
+pub enum ReserveRule {
+  /// a fixed reserve pressure expressed in psi
+  Pressure(f64),
+  /// one third of the starting pressure is kept as reserve
+  Thirds,
+}
+
+/// phase of the dive a gas segment belongs to
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GasPhase {
+  /// travel from the surface to the bottom
+  Descent,
+  /// remainder of the bottom time after the descent
+  Bottom,
+  /// travel to the first stop, between stops and to the surface
+  Travel,
+  /// a decompression stop
+  Stop,
+}
+
+/// gas used during a segment of the dive
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
+pub struct GasSegment {
+  /// phase of the dive
+  pub phase: GasPhase,
+  /// average depth of the segment expressed in feet of sea water
+  pub depth: f64,
+  /// duration of the segment expressed in minutes
+  pub minutes: f64,
+  /// gas used during the segment expressed in standard cubic feet
+  pub volume: f64,
+}
+
+/// the gas plan of a scuba dive
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct GasPlan {
+  /// gas used by every segment of the dive in chronological order
+  pub segments: Vec<GasSegment>,
+  /// gas used during the whole dive expressed in standard cubic feet
+  pub total_volume: f64,
+  /// cylinder pressure on surfacing expressed in psi, negative when the
+  /// cylinder does not hold enough gas for the dive
+  pub remaining_pressure: f64,
+  /// the reserve pressure expressed in psi
+  pub reserve_pressure: f64,
+  /// the cylinder still holds the reserve on surfacing
+  pub reserve_respected: bool,
+  /// the advisories relevant to the gas plan
+  pub advisories: Vec<Advisory>,
+}
+
+impl Cylinder {
+  pub fn new(capacity: f64, rated_pressure: f64, pressure: f64) -> Self {
+    //! Instantiates a new Cylinder object
+    //! the capacity is expressed in standard cubic feet at the rated pressure
+    //! the rated_pressure and pressure are expressed in psi
+    Self {
+      capacity,
+      rated_pressure,
+      pressure,
+    }
+  }
+
+  pub fn volume(self, pressure: f64) -> f64 {
+    //! returns the gas held at the given pressure expressed in standard
+    //! cubic feet
+    self.capacity * pressure / self.rated_pressure
+  }
+
+  pub fn pressure_drop(self, volume: f64) -> f64 {
+    //! returns the pressure drop caused by using the given volume of gas
+    //! expressed in psi
+    volume * self.rated_pressure / self.capacity
+  }
+}
+
+impl ReserveRule {
+  pub fn reserve_pressure(self, starting_pressure: f64) -> f64 {
+    //! returns the reserve pressure for the given starting pressure
+    //! expressed in psi
+    match self {
+      ReserveRule::Pressure(psi) => psi,
+      ReserveRule::Thirds => starting_pressure / 3.0,
+    }
+  }
+}
+
+impl GasSegment {
+  pub fn new(phase: GasPhase, depth: f64, minutes: f64, consumption_rate: f64) -> Self {
+    //! Instantiates a new GasSegment object
+    //! the depth is expressed in feet of sea water
+    //! the consumption_rate is the surface consumption rate expressed in
+    //! standard cubic feet per minute
+    Self {
+      phase,
+      depth,
+      minutes,
+      volume: consumption_rate * (depth + 33.0) / 33.0 * minutes,
+    }
+  }
+}
+
+/// dive_segments() returns the descent, bottom, travel and decompression stop
+/// segments of a dive breathed at the given surface consumption rate. the
+/// stops are taken from the air decompression schedule when the bottom time
+/// exceeds the no decompression limit
+pub fn dive_segments(dive: Dive, consumption_rate: f64) -> Vec<GasSegment> {
+  scheduled_dive_segments(dive, dive, consumption_rate)
+}
+
+/// scheduled_dive_segments() returns the segments of a dive breathed at the
+/// given surface consumption rate following the decompression of another
+/// dive, such as a repetitive dive following the schedule of its equivalent
+/// single dive. the descent and bottom segments last the bottom time of the
+/// dive while the stops are taken from the air decompression schedule of
+/// the scheduled dive when its bottom time exceeds the no decompression limit
+pub fn scheduled_dive_segments(dive: Dive, schedule: Dive, consumption_rate: f64) -> Vec<GasSegment> {
+  let depth = f64::from(dive.depth);
+  let descent = (depth / DESCENT_RATE).min(f64::from(dive.bottom_time));
+
+  let mut stops: Vec<DecoStops> = if schedule.requires_decompression() {
+    schedule.deco_dive().air_deco_stops
+  } else {
+    vec![]
+  };
+  stops.sort_by_key(|stop| std::cmp::Reverse(stop.depth));
+
+  let mut segments = vec![
+    GasSegment::new(GasPhase::Descent, depth / 2.0, descent, consumption_rate),
+    GasSegment::new(GasPhase::Bottom, depth, f64::from(dive.bottom_time) - descent, consumption_rate),
+  ];
+
+  let mut current = depth;
+  for stop in stops.iter() {
+    let stop_depth = f64::from(stop.depth);
+    segments.push(GasSegment::new(GasPhase::Travel, (current + stop_depth) / 2.0, (current - stop_depth) / ASCENT_RATE, consumption_rate));
+    segments.push(GasSegment::new(GasPhase::Stop, stop_depth, f64::from(stop.time), consumption_rate));
+    current = stop_depth;
+  }
+  segments.push(GasSegment::new(GasPhase::Travel, current / 2.0, current / ASCENT_RATE, consumption_rate));
+
+  segments
+}
+
+/// scuba_gas_plan() returns the gas plan of a scuba dive breathed at the
+/// given surface consumption rate, expressed in standard cubic feet per
+/// minute, from the given cylinder while keeping the reserve
+pub fn scuba_gas_plan(dive: Dive, consumption_rate: f64, cylinder: Cylinder, reserve: ReserveRule) -> GasPlan {
+  let segments = dive_segments(dive, consumption_rate);
+  let total_volume = segments.iter().map(|segment| segment.volume).sum();
+  let remaining_pressure = cylinder.pressure - cylinder.pressure_drop(total_volume);
+  let reserve_pressure = reserve.reserve_pressure(cylinder.pressure);
+  let reserve_respected = remaining_pressure >= reserve_pressure;
+
+  let mut advisories = vec![];
+  if !reserve_respected {
+    advisories.push(Advisory::new(AdvisoryCode::GasReserveBroken));
+  }
+
+  GasPlan {
+    segments,
+    total_volume,
+    remaining_pressure,
+    reserve_pressure,
+    reserve_respected,
+    advisories,
+  }
+}
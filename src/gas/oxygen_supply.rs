@@ -0,0 +1,136 @@
+use crate::airtables::{RowDeco, TreatmentGas};
+use crate::oxygen::{chamber_stops, in_water_o2_stops, tender_o2_minutes};
+use crate::treatment::TreatmentSchedule;
+
+/// who breathes the oxygen of a demand segment
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OxygenUse {
+  /// a diver breathing oxygen at an in-water stop
+  InWater,
+  /// a diver breathing oxygen in the chamber
+  ChamberDiver,
+  /// the inside tender breathing oxygen in the chamber
+  ChamberTender,
+}
+
+/// oxygen breathed during a period at constant depth
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
+pub struct OxygenDemand {
+  /// who breathes the oxygen
+  pub usage: OxygenUse,
+  /// depth of the period expressed in feet of sea water
+  pub depth: u16,
+  /// duration of the period expressed in minutes
+  pub minutes: u16,
+  /// oxygen used during the period expressed in standard cubic feet
+  pub volume: f64,
+}
+
+/// the oxygen quantity needed for the decompression of a dive day
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct OxygenQuantity {
+  /// oxygen consumption of a diver at in-water stops expressed in actual
+  /// cubic feet per minute
+  pub in_water_rate: f64,
+  /// oxygen consumption of a person breathing on the chamber built-in
+  /// breathing system expressed in actual cubic feet per minute
+  pub chamber_rate: f64,
+  /// the oxygen demand of every period added to the plan
+  pub demand: Vec<OxygenDemand>,
+}
+
+/// the oxygen to load for the dive day
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
+pub struct OxygenLoad {
+  /// oxygen required for the day expressed in standard cubic feet
+  pub total_volume: f64,
+  /// capacity of a single cylinder expressed in standard cubic feet
+  pub cylinder_capacity: f64,
+  /// number of full cylinders required to hold the total volume
+  pub cylinders: u16,
+}
+
+impl OxygenQuantity {
+  pub fn new(in_water_rate: f64, chamber_rate: f64) -> Self {
+    //! Instantiates a new OxygenQuantity object with no demand
+    //! the in_water_rate and chamber_rate are expressed in actual cubic
+    //! feet per minute
+    Self {
+      in_water_rate,
+      chamber_rate,
+      demand: vec![],
+    }
+  }
+
+  pub fn add_period(&mut self, usage: OxygenUse, depth: u16, minutes: u16) {
+    //! adds a period of oxygen breathing at a depth expressed in feet of sea
+    //! water for the given minutes
+    let rate = match usage {
+      OxygenUse::InWater => self.in_water_rate,
+      OxygenUse::ChamberDiver | OxygenUse::ChamberTender => self.chamber_rate,
+    };
+
+    self.demand.push(OxygenDemand {
+      usage,
+      depth,
+      minutes,
+      volume: rate * (f64::from(depth) + 33.0) / 33.0 * f64::from(minutes),
+    });
+  }
+
+  pub fn add_o2_stops(&mut self, schedule: &RowDeco) {
+    //! adds the in-water oxygen stops of a decompression schedule
+    for stop in in_water_o2_stops(schedule) {
+      self.add_period(OxygenUse::InWater, stop.depth, stop.time);
+    }
+  }
+
+  pub fn add_chamber_periods(&mut self, o2cp: f32) {
+    //! adds the SurDO2 chamber periods of a decompression schedule along
+    //! with the oxygen breathed by the inside tender
+    let stops = chamber_stops(o2cp);
+
+    for stop in stops.iter() {
+      self.add_period(OxygenUse::ChamberDiver, stop.depth, stop.time);
+    }
+    if let Some(last) = stops.last() {
+      self.add_period(OxygenUse::ChamberTender, last.depth, tender_o2_minutes(o2cp));
+    }
+  }
+
+  pub fn add_treatment(&mut self, schedule: &TreatmentSchedule) {
+    //! adds the oxygen breathed by the patient and the inside tender during
+    //! a recompression treatment. travel steps are accounted at their mean
+    //! depth. the steps breathing air or oxygen periods are conservatively
+    //! accounted as oxygen for their whole duration
+    let patient = schedule.timeline.iter()
+      .filter(|entry| matches!(entry.gas, TreatmentGas::Oxygen | TreatmentGas::AirOrOxygen))
+      .map(|entry| (OxygenUse::ChamberDiver, entry));
+    let tender = schedule.tender_o2.iter()
+      .map(|entry| (OxygenUse::ChamberTender, entry));
+
+    for (usage, entry) in patient.chain(tender) {
+      self.add_period(usage, (entry.start_depth + entry.end_depth) / 2, entry.end - entry.start);
+    }
+  }
+
+  pub fn total_volume(&self) -> f64 {
+    //! returns the oxygen required by all the periods expressed in standard
+    //! cubic feet
+    self.demand.iter().map(|period| period.volume).sum()
+  }
+
+  pub fn load(&self, cylinder_capacity: f64) -> OxygenLoad {
+    //! returns the total volume and the number of cylinders of the given
+    //! capacity, expressed in standard cubic feet, to load for the day
+    let total_volume = self.total_volume();
+
+    OxygenLoad {
+      total_volume,
+      cylinder_capacity,
+      cylinders: (total_volume / cylinder_capacity).ceil() as u16,
+    }
+  }
+}
+
@@ -0,0 +1,170 @@
+use crate::{Dive, DivePlan};
+use crate::gas::scheduled_dive_segments;
+
+/// pressure exerted by one foot of sea water expressed in psi
+pub const PSI_PER_FSW: f64 = 0.445;
+
+/// atmospheric pressure expressed in psi
+pub const ATMOSPHERIC_PRESSURE: f64 = 14.7;
+
+/// a dive made on the supply
+#[derive(Serialize, Deserialize, Copy, Clone, Debug)]
+pub struct SupplyDive {
+  /// the dive as breathed, made of its depth and actual bottom time
+  pub dive: Dive,
+  /// the dive whose air decompression schedule is followed, the equivalent
+  /// single dive of a repetitive dive
+  pub schedule: Dive,
+}
+
+/// a surface-supplied diver and the dives they will make on the supply
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SupplyDiver {
+  /// the dives made by the diver
+  pub dives: Vec<SupplyDive>,
+}
+
+/// a high-pressure air bank
+#[derive(Serialize, Deserialize, Copy, Clone, Debug)]
+pub struct AirBank {
+  /// floodable volume of a single cylinder expressed in cubic feet
+  pub floodable_volume: f64,
+  /// number of cylinders in the bank
+  pub cylinders: u16,
+  /// current pressure of the bank expressed in psi
+  pub pressure: f64,
+}
+
+/// the source supplying air to the divers
+#[derive(Serialize, Deserialize, Copy, Clone, Debug)]
+#[serde(tag = "source", rename_all = "snake_case")]
+pub enum AirSource {
+  /// a compressor delivering a constant flow
+  Compressor {
+    /// delivered flow expressed in standard cubic feet per minute
+    capacity: f64,
+  },
+  /// a high-pressure air bank
+  Bank(AirBank),
+}
+
+/// the air demand of the divers and whether the source can meet it
+#[derive(Serialize, Deserialize, Copy, Clone, Debug)]
+pub struct SupplyCheck {
+  /// flow required with every diver at their deepest depth expressed in
+  /// standard cubic feet per minute
+  pub required_flow: f64,
+  /// air required by all the dives expressed in standard cubic feet
+  pub required_volume: f64,
+  /// minimum manifold pressure required at the deepest depth expressed in psi
+  pub minimum_manifold_pressure: f64,
+  /// flow delivered by a compressor, or air available from a bank above the
+  /// minimum manifold pressure, in the units of the matching requirement
+  pub available: f64,
+  /// the source meets the demand
+  pub sufficient: bool,
+  /// what is left of the source once the demand is met, in the units of
+  /// the available figure. negative when the source is not sufficient
+  pub margin: f64,
+}
+
+impl SupplyDive {
+  pub fn single(dive: Dive) -> Self {
+    //! Instantiates a new SupplyDive object following its own schedule
+    Self {
+      dive,
+      schedule: dive,
+    }
+  }
+}
+
+impl SupplyDiver {
+  pub fn from_dive(dive: Dive) -> Self {
+    //! Instantiates a new SupplyDiver object making a single dive
+    Self {
+      dives: vec![SupplyDive::single(dive)],
+    }
+  }
+
+  pub fn from_plan(plan: DivePlan, next_bottom_time: u16) -> Self {
+    //! Instantiates a new SupplyDiver object making the first dive of a
+    //! DivePlan object and a repetitive dive with the given bottom time
+    //! expressed in minutes. the repetitive dive breathes its actual bottom
+    //! time and follows the schedule of its equivalent single dive
+    Self {
+      dives: vec![
+        SupplyDive::single(Dive::new(plan.depth, plan.bottom_time).with_exposure(plan.exposure)),
+        SupplyDive {
+          dive: Dive::new(plan.next_dive_depth, next_bottom_time).with_exposure(plan.exposure),
+          schedule: plan.repetitive_dive(next_bottom_time),
+        },
+      ],
+    }
+  }
+
+  pub fn max_depth(&self) -> u16 {
+    //! returns the deepest depth of the dives expressed in feet of sea water
+    self.dives.iter().map(|supply_dive| supply_dive.dive.depth).max().unwrap_or(0)
+  }
+}
+
+impl AirBank {
+  pub fn new(floodable_volume: f64, cylinders: u16, pressure: f64) -> Self {
+    //! Instantiates a new AirBank object
+    //! the floodable_volume is expressed in cubic feet per cylinder
+    //! the pressure is expressed in psi
+    Self {
+      floodable_volume,
+      cylinders,
+      pressure,
+    }
+  }
+
+  pub fn available_volume(self, minimum_manifold_pressure: f64) -> f64 {
+    //! returns the air available above the minimum manifold pressure
+    //! expressed in standard cubic feet
+    (self.pressure - minimum_manifold_pressure) / ATMOSPHERIC_PRESSURE
+      * self.floodable_volume * f64::from(self.cylinders)
+  }
+}
+
+/// minimum_manifold_pressure() returns the pressure the supply must deliver
+/// to a diver at the given depth, expressed in feet of sea water, with the
+/// over-bottom pressure of the equipment, expressed in psi
+pub fn minimum_manifold_pressure(depth: u16, over_bottom_pressure: f64) -> f64 {
+  PSI_PER_FSW * f64::from(depth) + over_bottom_pressure
+}
+
+/// supply_check() returns the air demand of the divers breathing the given
+/// umbilical supply rate, expressed in actual cubic feet per minute, and
+/// whether the source can meet it. a compressor must deliver the flow of
+/// all the divers at their deepest depth while a bank must hold the air of
+/// all the dives, decompression included, above the minimum manifold pressure
+pub fn supply_check(divers: &[SupplyDiver], supply_rate: f64, source: AirSource, over_bottom_pressure: f64) -> SupplyCheck {
+  let max_depth = divers.iter().map(|diver| diver.max_depth()).max().unwrap_or(0);
+  let minimum_manifold_pressure = minimum_manifold_pressure(max_depth, over_bottom_pressure);
+
+  let required_flow = divers.iter()
+    .map(|diver| supply_rate * (f64::from(diver.max_depth()) + 33.0) / 33.0)
+    .sum();
+
+  let required_volume = divers.iter()
+    .flat_map(|diver| diver.dives.iter())
+    .flat_map(|supply_dive| scheduled_dive_segments(supply_dive.dive, supply_dive.schedule, supply_rate))
+    .map(|segment| segment.volume)
+    .sum();
+
+  let (available, required) = match source {
+    AirSource::Compressor { capacity } => (capacity, required_flow),
+    AirSource::Bank(bank) => (bank.available_volume(minimum_manifold_pressure).max(0.0), required_volume),
+  };
+
+  SupplyCheck {
+    required_flow,
+    required_volume,
+    minimum_manifold_pressure,
+    available,
+    sufficient: available >= required,
+    margin: available - required,
+  }
+}
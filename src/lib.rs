@@ -59,13 +59,66 @@
 #![deny(missing_docs)]
 #![deny(dead_code)]
 #[macro_use]
-
 extern crate serde_derive;
 
+/// this module provides the advisories raised by the calculations
+pub mod advisories;
 /// this module provides functionality for the US Navy dive tables
 pub mod airtables;
+/// this module provides the routing of the JSON API
+pub mod api;
+/// this module provides the procedures for decompression contingencies
+pub mod contingencies;
+/// this module provides the plain text language of dive plans
+pub mod dsl;
+/// this module provides the errors returned by the calculations
+pub mod errors;
+/// this module provides the import and export of dive logs
+pub mod formats;
+/// this module provides the gas planning of dives
+pub mod gas;
+/// this module provides the real time monitoring of a dive from depth samples
+pub mod monitor;
+/// this module provides enriched air nitrox planning on the air tables
+pub mod nitrox;
+/// this module provides the oxygen exposure tracking of a dive day
+pub mod oxygen;
+/// this module provides the reduction of variable depth dive profiles
+pub mod profile;
+/// this module provides the timekeeping of the divers followed by a dive station
+pub mod station;
 /// this module provides a unit test suite for the calculations
 pub mod tests;
+/// this module provides the dive records kept by the timekeeper
+pub mod timekeeping;
+/// this module provides the schedules of the recompression treatment tables
+pub mod treatment;
+/// this module provides the conversion of depths between units
+pub mod units;
+
+use advisories::{Advised, Advisory, AdvisoryCode};
+
+/// conditions of a dive that require the next longer bottom time schedule
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ExposureModifier {
+  /// the dive is neither unusually cold nor strenuous
+  #[default]
+  None,
+  /// the diver was unusually cold during the dive
+  Cold,
+  /// the workload during the dive was strenuous
+  Strenuous,
+  /// the dive was unusually cold and the workload strenuous
+  ColdAndStrenuous,
+}
+
+impl ExposureModifier {
+  pub fn is_adjusted(self) -> bool {
+    //! returns true when the next longer bottom time schedule must be used
+    self != ExposureModifier::None
+  }
+}
 
 /// single dive object
 #[derive(Serialize, Deserialize, Copy, Clone, Debug)]
@@ -74,6 +127,9 @@ pub struct Dive {
   pub depth: u16, 
   /// bottom time of the dive expressed in minutes
   pub bottom_time: u16,
+  /// conditions of the dive that require the next longer bottom time schedule
+  #[serde(default)]
+  pub exposure: ExposureModifier,
 }
 
 /// single dive plan object
@@ -87,6 +143,10 @@ pub struct DivePlan {
   pub surface_interval_time: u16,
   /// the depth of the next planned dive expressed in feet of sea water
   pub next_dive_depth: u16,
+  /// conditions of the first dive that require the next longer bottom
+  /// time schedule
+  #[serde(default)]
+  pub exposure: ExposureModifier,
 }
 
 impl Dive {
@@ -95,6 +155,16 @@ impl Dive {
     Self {
       depth,
       bottom_time,
+      exposure: ExposureModifier::None,
+    }
+  }
+
+  pub fn with_exposure(self, exposure: ExposureModifier) -> Self {
+    //! returns the Dive object with the given exposure modifier. unusually
+    //! cold or strenuous dives use the next longer bottom time schedule
+    Self {
+      exposure,
+      ..self
     }
   }
 
@@ -117,7 +187,7 @@ impl Dive {
       }
     }
 
-    return ndl;
+    ndl
   }
 
   pub fn group_letter(self) -> String {
@@ -126,6 +196,7 @@ impl Dive {
     //! the bottom_time is expressed in minutes
     //! the group letter is returned as a String
     let mut gl: String = String::from("");
+    let bottom_time = nodeco_bottom_time(self.depth, self.bottom_time, self.exposure);
 
     let nodeco_table = airtables::nodeco_table()
       .expect("Error serializing the data within the deco_table");
@@ -133,28 +204,28 @@ impl Dive {
     for row in nodeco_table.table_data.iter() {
       if row.min_fsw <= self.depth && self.depth <= row.max_fsw {
         for value in row.values.iter() {
-          if value.min_time <= self.bottom_time && self.bottom_time <= value.max_time {
+          if value.min_time <= bottom_time && bottom_time <= value.max_time {
             gl = String::from(&value.group_letter);
           } 
         }
       }
     }
     
-    if gl == String::from("") && self.depth > 0 && self.depth <= 10 && self.bottom_time > 462 {
+    if gl.is_empty() && self.depth > 0 && self.depth <= 10 && bottom_time > 462 {
       gl = String::from("F")
-    } else if gl == String::from("") && self.depth > 10 && self.depth <= 15 && self.bottom_time > 449 {
+    } else if gl.is_empty() && self.depth > 10 && self.depth <= 15 && bottom_time > 449 {
       gl = String::from("I")
-    } else if gl == String::from("") && self.depth > 15 && self.depth <= 20 && self.bottom_time > 461 {
+    } else if gl.is_empty() && self.depth > 15 && self.depth <= 20 && bottom_time > 461 {
       gl = String::from("L")
     }
 
-    if gl == String::from("") && self.depth <= 190 {
+    if gl.is_empty() && self.depth <= 190 {
       gl = String::from("this dive is out of the time range for no-decompression air dives")
-    } else if gl == String::from("") && self.depth > 190 {
+    } else if gl.is_empty() && self.depth > 190 {
       gl = String::from("this dive is out of the depth range for no-decompression air dives")
     }
 
-    return gl;
+    gl
   }
 
   pub fn deco_dive(self) -> airtables::RowDeco {
@@ -165,6 +236,8 @@ impl Dive {
     let deco_table = airtables::deco_table()
       .expect("Error deserializing no decompression table");
 
+    let bottom_time = deco_bottom_time(self.depth, self.bottom_time, self.exposure);
+
     let mut deco_profile: airtables::RowDeco = airtables::RowDeco {
       min_time: 0,
       max_time: 0,
@@ -184,13 +257,96 @@ impl Dive {
     for row_deco in deco_table.table_data.iter() {
       if row_deco.min_fsw <= self.depth && self.depth <= row_deco.max_fsw  {
         for profile in row_deco.rows.iter() {
-          if profile.min_time <= self.bottom_time && profile.max_time <= self.bottom_time {
+          if profile.min_time <= bottom_time && bottom_time <= profile.max_time {
             deco_profile = profile.clone()
           }
         }
       }
     }
-    return deco_profile;
+    deco_profile
+  }
+
+  pub fn deco_table_depth(self) -> u16 {
+    //! returns the depth of the air decompression table row of the Dive
+    //! object expressed in feet of sea water, 0 when the depth is outside
+    //! the table
+    let deco_table = airtables::deco_table()
+      .expect("Error deserializing decompression table");
+
+    deco_table.table_data.iter()
+      .find(|row_deco| row_deco.min_fsw <= self.depth && self.depth <= row_deco.max_fsw)
+      .map_or(0, |row_deco| row_deco.max_fsw)
+  }
+
+  pub fn advised_no_decompression_limit(self) -> Advised<u16> {
+    //! returns the no decompression limit of the Dive object along with
+    //! the advisories relevant to the dive
+    Advised::new(self.no_decompression_limit(), self.no_decompression_advisories())
+  }
+
+  pub fn advised_group_letter(self) -> Advised<String> {
+    //! returns the group letter of the Dive object along with the
+    //! advisories relevant to the dive
+    Advised::new(self.group_letter(), self.no_decompression_advisories())
+  }
+
+  pub fn end_group_letter(self) -> String {
+    //! returns the repetitive group letter at the end of the Dive object:
+    //! the group of the no-decompression table, or the group of the air
+    //! decompression schedule when the dive requires decompression
+    if self.requires_decompression() {
+      self.deco_dive().repetgroup_letter
+    } else {
+      self.group_letter()
+    }
+  }
+
+  pub fn requires_decompression(self) -> bool {
+    //! returns true when the Dive object needs an air decompression
+    //! schedule: the bottom time exceeds the no decompression limit or the
+    //! depth exceeds the no-decompression table
+    let advisories = self.no_decompression_advisories();
+    advisories.iter().any(|advisory| matches!(
+      advisory.code,
+      AdvisoryCode::NoDecompressionLimitExceeded | AdvisoryCode::OutsideNoDecompressionTable
+    ))
+  }
+
+  pub fn advised_deco_dive(self) -> Advised<airtables::RowDeco> {
+    //! returns the decompression profile of the Dive object along with
+    //! the advisories relevant to the dive and its schedule. a dive within
+    //! the no decompression limit has an empty profile flagged with the
+    //! NoDecompressionRequired advisory
+    let deco_profile = self.deco_dive();
+    let mut advisories = advisories::depth_advisories(self.depth);
+
+    if deco_profile.max_time == 0 && !self.requires_decompression() {
+      advisories.push(Advisory::new(AdvisoryCode::NoDecompressionRequired));
+    } else if deco_profile.max_time == 0 {
+      advisories.push(Advisory::new(AdvisoryCode::OutsideDecompressionTable));
+    } else {
+      advisories.extend(deco_profile.advisories());
+    }
+    if self.exposure.is_adjusted() {
+      advisories.push(Advisory::new(AdvisoryCode::ScheduleAdjusted));
+    }
+
+    Advised::new(deco_profile, advisories)
+  }
+
+  fn no_decompression_advisories(self) -> Vec<Advisory> {
+    let mut advisories = advisories::depth_advisories(self.depth);
+
+    if self.depth > 190 {
+      advisories.push(Advisory::new(AdvisoryCode::OutsideNoDecompressionTable));
+    } else if nodeco_bottom_time(self.depth, self.bottom_time, self.exposure) > self.no_decompression_limit() {
+      advisories.push(Advisory::new(AdvisoryCode::NoDecompressionLimitExceeded));
+    }
+    if self.exposure.is_adjusted() {
+      advisories.push(Advisory::new(AdvisoryCode::ScheduleAdjusted));
+    }
+
+    advisories
   }
 }
 
@@ -205,6 +361,7 @@ impl DivePlan {
       bottom_time,
       surface_interval_time,
       next_dive_depth,
+      exposure: ExposureModifier::None,
     }
   }
 
@@ -218,6 +375,17 @@ impl DivePlan {
       bottom_time: dive.bottom_time,
       surface_interval_time,
       next_dive_depth,
+      exposure: dive.exposure,
+    }
+  }
+
+  pub fn with_exposure(self, exposure: ExposureModifier) -> Self {
+    //! returns the DivePlan object with the given exposure modifier for the
+    //! first dive. unusually cold or strenuous dives use the next longer
+    //! bottom time schedule
+    Self {
+      exposure,
+      ..self
     }
   }
 
@@ -239,7 +407,7 @@ impl DivePlan {
       }
     }
 
-    return ndl;
+    ndl
   }
 
   pub fn group_letter(self) -> String {
@@ -248,6 +416,7 @@ impl DivePlan {
     //! the bottom_time is expressed in minutes
     //! the group letter is returned as a String
     let mut gl: String = String::from("");
+    let bottom_time = nodeco_bottom_time(self.depth, self.bottom_time, self.exposure);
 
     let nodeco_table = airtables::nodeco_table()
       .expect("Error serializing the data within the deco_table");
@@ -255,97 +424,158 @@ impl DivePlan {
     for row in nodeco_table.table_data.iter() {
       if row.min_fsw <= self.depth && self.depth <= row.max_fsw {
         for value in row.values.iter() {
-          if value.min_time <= self.bottom_time && self.bottom_time <= value.max_time {
+          if value.min_time <= bottom_time && bottom_time <= value.max_time {
             gl = String::from(&value.group_letter);
           } 
         }
       }
     }
     
-    if gl == String::from("") && self.depth > 0 && self.depth <= 10 && self.bottom_time > 462 {
+    if gl.is_empty() && self.depth > 0 && self.depth <= 10 && bottom_time > 462 {
       gl = String::from("F")
-    } else if gl == String::from("") && self.depth > 10 && self.depth <= 15 && self.bottom_time > 449 {
+    } else if gl.is_empty() && self.depth > 10 && self.depth <= 15 && bottom_time > 449 {
       gl = String::from("I")
-    } else if gl == String::from("") && self.depth > 15 && self.depth <= 20 && self.bottom_time > 461 {
+    } else if gl.is_empty() && self.depth > 15 && self.depth <= 20 && bottom_time > 461 {
       gl = String::from("L")
     }
 
-    if gl == String::from("") && self.depth <= 190 {
+    if gl.is_empty() && self.depth <= 190 {
       gl = String::from("this dive is out of the time range for no-decompression air dives")
-    } else if gl == String::from("") && self.depth > 190 {
+    } else if gl.is_empty() && self.depth > 190 {
       gl = String::from("this dive is out of the depth range for no-decompression air dives")
     }
-    return gl;
+    gl
   }
 
   pub fn repet_letter(self) -> String {
     //! Returns the repetitive group letter of the DivePlan object. 
     //! the depth and next_dive_depth are expressed in feet of sea water
     //! the bottom_time and surface_interval_time are expressed in minutes
-    //! the repetitive group letter is returned as a String
-    let nodeco_table = airtables::nodeco_table()
-      .expect("Error serializing the data within the deco_table");
-
+    //! the repetitive group letter is returned as a String, empty when the
+    //! plan is outside the repetitive dive tables
     let rgl_table = airtables::rgl_table()
       .expect("there was an error deserializing deco table");
-  
-    let mut rl = String::new();
-  
-    for row in nodeco_table.table_data.iter() {
-      if row.min_fsw <= self.depth && self.depth <= row.max_fsw {
-        for group in row.values.iter() {
-          if group.min_time <= self.bottom_time && self.bottom_time <= group.max_time {
-            for rgl_row in rgl_table.table_data.iter() {
-              if rgl_row.group_letter == group.group_letter && rgl_row.min_time <= self.surface_interval_time && self.surface_interval_time <= rgl_row.max_time {
-                rl = String::from(&rgl_row.repet_letter)
-              }
-            }
-          } 
-        }
-      }
-    }
 
-    return rl;
+    let group_letter = self.first_dive().end_group_letter();
+
+    rgl_table.table_data.iter()
+      .find(|rgl_row| {
+        rgl_row.group_letter == group_letter
+          && rgl_row.min_time <= self.surface_interval_time
+          && self.surface_interval_time <= rgl_row.max_time
+      })
+      .map_or_else(String::new, |rgl_row| String::from(&rgl_row.repet_letter))
   }
 
   pub fn residual_nitrogen_time(self) -> u16 {
     //! Returns the residual nitrogen time of the DivePlan object. 
     //! the depth and next_dive_depth are expressed in feet of sea water
     //! the bottom_time and surface_interval_time are expressed in minutes
-    //! the residual nitrogen time is returned as a u16 integer
-    let nodeco_table = airtables::nodeco_table()
-      .expect("Error deserializing no decompression table");
-
-    let rgl_table = airtables::rgl_table()
-      .expect("Error deserializing repetitive group letter table");
-
+    //! the residual nitrogen time is returned as a u16 integer, 0 when the
+    //! plan is outside the repetitive dive tables
     let rnt_table = airtables::rnt_table()
       .expect("Error deserializing residual nitrogen time table");
-  
-    let mut rnt = 0;
-    
-    for row in nodeco_table.table_data.iter() {
-      if row.min_fsw <= self.depth && self.depth <= row.max_fsw {
-        for group in row.values.iter() {
-          if group.min_time <= self.bottom_time && self.bottom_time <= group.max_time {
-            for rgl_row in rgl_table.table_data.iter() {
-              if rgl_row.group_letter == group.group_letter && rgl_row.min_time <= self.surface_interval_time && self.surface_interval_time <= rgl_row.max_time {
-                for rnt_column in rnt_table.table_data.iter() {
-                  if rnt_column.repet_letter == rgl_row.repet_letter {
-                    for element in rnt_column.rnt.iter() {
-                      if element.min_depth <= self.next_dive_depth && self.next_dive_depth <= element.max_depth {
-                        rnt = element.rnt
-                      } 
-                    }
-                  }
-                }
-              }
-            }
-          } 
-        }
-      }
+
+    let repet_letter = self.repet_letter();
+
+    rnt_table.table_data.iter()
+      .filter(|rnt_column| rnt_column.repet_letter == repet_letter)
+      .flat_map(|rnt_column| rnt_column.rnt.iter())
+      .find(|element| element.min_depth <= self.next_dive_depth && self.next_dive_depth <= element.max_depth)
+      .map_or(0, |element| element.rnt)
+  }
+
+  pub fn repetitive_dive(self, bottom_time: u16) -> Dive {
+    //! returns the equivalent single dive of the repetitive dive of the
+    //! DivePlan object. the bottom_time of the repetitive dive is expressed
+    //! in minutes and the equivalent single dive is made at the
+    //! next_dive_depth for the residual nitrogen time plus the bottom_time,
+    //! under the exposure modifier of the DivePlan object
+    Dive::new(self.next_dive_depth, self.residual_nitrogen_time().saturating_add(bottom_time)).with_exposure(self.exposure)
+  }
+
+  pub fn advised_no_decompression_limit(self) -> Advised<u16> {
+    //! returns the no decompression limit for the first dive of a DivePlan
+    //! object along with the advisories relevant to the first dive
+    self.first_dive().advised_no_decompression_limit()
+  }
+
+  pub fn advised_group_letter(self) -> Advised<String> {
+    //! returns the group letter of the first dive of a DivePlan object
+    //! along with the advisories relevant to the first dive
+    self.first_dive().advised_group_letter()
+  }
+
+  pub fn advised_repet_letter(self) -> Advised<String> {
+    //! Returns the repetitive group letter of the DivePlan object along
+    //! with the advisories relevant to the dive plan
+    let rl = self.repet_letter();
+    let advisories = self.repetitive_advisories(rl.is_empty());
+    Advised::new(rl, advisories)
+  }
+
+  pub fn advised_residual_nitrogen_time(self) -> Advised<u16> {
+    //! Returns the residual nitrogen time of the DivePlan object along
+    //! with the advisories relevant to the dive plan
+    let outside = self.repet_letter().is_empty() || self.next_dive_depth > 190;
+    Advised::new(self.residual_nitrogen_time(), self.repetitive_advisories(outside))
+  }
+
+  fn first_dive(self) -> Dive {
+    Dive::new(self.depth, self.bottom_time).with_exposure(self.exposure)
+  }
+
+  fn repetitive_advisories(self, outside_tables: bool) -> Vec<Advisory> {
+    let mut advisories = advisories::depth_advisories(self.depth.max(self.next_dive_depth));
+
+    if self.first_dive().end_group_letter() == "N/A" {
+      advisories.push(Advisory::new(AdvisoryCode::RepetitiveDiveNotPermitted));
+    } else if outside_tables {
+      advisories.push(Advisory::new(AdvisoryCode::OutsideRepetitiveTables));
     }
-  
-    return rnt;
+    if self.exposure.is_adjusted() {
+      advisories.push(Advisory::new(AdvisoryCode::ScheduleAdjusted));
+    }
+
+    advisories
+  }
+}
+
+/// returns the bottom time used to look up the no-decompression table. when
+/// the dive is unusually cold or strenuous this is the first minute of the
+/// next longer group of the depth row
+fn nodeco_bottom_time(depth: u16, bottom_time: u16, exposure: ExposureModifier) -> u16 {
+  if !exposure.is_adjusted() {
+    return bottom_time;
   }
+
+  let nodeco_table = airtables::nodeco_table()
+    .expect("Error deserializing no decompression table");
+
+  nodeco_table.table_data.iter()
+    .filter(|row| row.min_fsw <= depth && depth <= row.max_fsw)
+    .flat_map(|row| row.values.iter())
+    .find(|group| group.min_time <= bottom_time && bottom_time <= group.max_time)
+    .map_or(bottom_time, |group| group.max_time.saturating_add(1))
+}
+
+/// returns the bottom time used to look up the air decompression table. when
+/// the dive is unusually cold or strenuous this is the first minute of the
+/// next longer schedule of the depth
+fn deco_bottom_time(depth: u16, bottom_time: u16, exposure: ExposureModifier) -> u16 {
+  if !exposure.is_adjusted() {
+    return bottom_time;
+  }
+
+  let deco_table = airtables::deco_table()
+    .expect("Error deserializing decompression table");
+
+  deco_table.table_data.iter()
+    .filter(|row_deco| row_deco.min_fsw <= depth && depth <= row_deco.max_fsw)
+    .flat_map(|row_deco| row_deco.rows.iter())
+    .find(|profile| profile.min_time <= bottom_time && bottom_time <= profile.max_time)
+    .map_or_else(
+      || nodeco_bottom_time(depth, bottom_time, exposure),
+      |profile| profile.max_time.saturating_add(1),
+    )
 }
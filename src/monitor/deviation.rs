@@ -0,0 +1,174 @@
+use crate::Dive;
+use crate::airtables::{DecoStops, RowDeco};
+use crate::errors::DiveError;
+use crate::monitor::{DiveMonitor, MonitorEvent, STOP_TOLERANCE};
+use crate::profile::DiveProfile;
+
+/// how a decompression stop of the plan deviated
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StopDeviationKind {
+  /// the diver spent less than the planned time at the stop
+  Shortened,
+  /// the diver never stopped at the stop depth
+  Skipped,
+}
+
+/// a decompression stop of the plan that was not completed
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct StopDeviation {
+  /// depth of the stop expressed in feet of sea water
+  pub depth: u16,
+  /// planned stop time expressed in minutes
+  pub planned: u16,
+  /// time actually spent at the stop expressed in seconds
+  pub actual: u32,
+  /// how the stop deviated from the plan
+  pub kind: StopDeviationKind,
+}
+
+/// an ascent faster than the allowed ascent rate
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
+pub struct AscentRateViolation {
+  /// elapsed time of the violation expressed in seconds
+  pub time: u32,
+  /// ascent rate expressed in feet of sea water per minute
+  pub rate: f64,
+}
+
+/// the follow up recommended after comparing a dive with its plan
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Recommendation {
+  /// the dive was carried out as planned
+  NoAction,
+  /// the decompression was completed but the dive went deeper, longer or
+  /// faster than planned. the diver is charged with the repetitive group
+  /// of the dive actually performed
+  Penalty {
+    /// repetitive group letter of the dive actually performed
+    repet_group: String,
+  },
+  /// decompression required by the dive actually performed was omitted
+  /// and the omitted decompression procedure applies
+  OmittedDecompression {
+    /// stop time left at each omitted stop rounded up to the next minute
+    omitted_stops: Vec<DecoStops>,
+  },
+}
+
+/// the comparison between a planned dive and its logged depth profile
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DeviationReport {
+  /// the planned table dive
+  pub planned: Dive,
+  /// the table dive reduced from the logged profile
+  pub actual: Dive,
+  /// bottom time in excess of the plan expressed in minutes
+  pub extra_bottom_time: u16,
+  /// depth in excess of the plan expressed in feet of sea water
+  pub extra_depth: u16,
+  /// planned stops that were shortened or skipped
+  pub stops: Vec<StopDeviation>,
+  /// ascents faster than the allowed ascent rate
+  pub ascent_rate_violations: Vec<AscentRateViolation>,
+  /// the recommended follow up
+  pub recommendation: Recommendation,
+}
+
+/// deviation_report() compares the planned dive, decompressed on the air
+/// schedule of the tables, with what the logged profile shows actually
+/// happened. when the dive went deeper or longer than planned the stops
+/// are also checked against the schedule of the dive actually performed
+pub fn deviation_report(planned: Dive, profile: &DiveProfile) -> Result<DeviationReport, DiveError> {
+  let reduction = profile.reduce()?;
+  let actual = Dive {
+    exposure: planned.exposure,
+    ..reduction.dive
+  };
+
+  let ascent = &profile.samples[profile.samples.iter()
+    .position(|sample| sample.time >= reduction.leave_bottom)
+    .unwrap_or(profile.samples.len())..];
+  let stop_time = |depth: u16| -> u32 {
+    let at_stop = |sample_depth: f64| (sample_depth - f64::from(depth)).abs() <= STOP_TOLERANCE;
+    ascent.windows(2)
+      .filter(|pair| at_stop(pair[0].depth) && at_stop(pair[1].depth))
+      .map(|pair| pair[1].time - pair[0].time)
+      .sum()
+  };
+
+  let planned_stops = air_stops(planned);
+  let stops = planned_stops.iter()
+    .map(|stop| (stop, stop_time(stop.depth)))
+    .filter(|(stop, actual)| *actual < u32::from(stop.time) * 60)
+    .map(|(stop, actual)| StopDeviation {
+      depth: stop.depth,
+      planned: stop.time,
+      actual,
+      kind: if actual == 0 { StopDeviationKind::Skipped } else { StopDeviationKind::Shortened },
+    })
+    .collect::<Vec<_>>();
+
+  let mut monitor = DiveMonitor::new();
+  let mut ascent_rate_violations = vec![];
+  for sample in profile.samples.iter() {
+    for event in monitor.update(sample.time, sample.depth)?.events {
+      if let MonitorEvent::AscentTooFast { time, rate } = event {
+        ascent_rate_violations.push(AscentRateViolation { time, rate });
+      }
+    }
+  }
+
+  let extra_bottom_time = actual.bottom_time.saturating_sub(planned.bottom_time);
+  let extra_depth = actual.depth.saturating_sub(planned.depth);
+  let governing = if extra_bottom_time > 0 || extra_depth > 0 { actual } else { planned };
+
+  let omitted_stops = air_stops(governing).into_iter()
+    .map(|stop| {
+      let left = (u32::from(stop.time) * 60).saturating_sub(stop_time(stop.depth));
+      DecoStops { depth: stop.depth, time: left.div_ceil(60) as u16 }
+    })
+    .filter(|stop| stop.time > 0)
+    .collect::<Vec<_>>();
+
+  let recommendation = if !omitted_stops.is_empty() {
+    Recommendation::OmittedDecompression { omitted_stops }
+  } else if extra_bottom_time > 0 || extra_depth > 0 || !ascent_rate_violations.is_empty() {
+    Recommendation::Penalty { repet_group: repet_group(actual) }
+  } else {
+    Recommendation::NoAction
+  };
+
+  Ok(DeviationReport {
+    planned,
+    actual,
+    extra_bottom_time,
+    extra_depth,
+    stops,
+    ascent_rate_violations,
+    recommendation,
+  })
+}
+
+/// returns the air decompression schedule of the dive, none when the dive
+/// stays within the no decompression limit
+fn schedule(dive: Dive) -> Option<RowDeco> {
+  if dive.requires_decompression() {
+    Some(dive.deco_dive())
+  } else {
+    None
+  }
+}
+
+/// returns the air decompression stops of the dive, deepest first
+fn air_stops(dive: Dive) -> Vec<DecoStops> {
+  let mut stops = schedule(dive).map_or_else(Vec::new, |schedule| schedule.air_deco_stops);
+  stops.sort_by_key(|stop| std::cmp::Reverse(stop.depth));
+  stops
+}
+
+/// returns the repetitive group letter the dive is charged with
+fn repet_group(dive: Dive) -> String {
+  schedule(dive).map_or_else(|| dive.group_letter(), |schedule| schedule.repetgroup_letter)
+}
@@ -0,0 +1,387 @@
+use crate::Dive;
+use crate::airtables::{DecoStops, RowDeco};
+use crate::errors::DiveError;
+use crate::gas::ASCENT_RATE;
+use crate::profile::{fsw, minutes, SURFACE_DEPTH};
+
+/// this module provides the comparison of a logged dive with its plan
+pub mod deviation;
+
+/// ascent rate above ASCENT_RATE tolerated before raising an event
+/// expressed in feet of sea water per minute
+pub const ASCENT_RATE_TOLERANCE: f64 = 10.0;
+
+/// distance from a stop depth within which the diver is considered at the
+/// stop expressed in feet of sea water
+pub const STOP_TOLERANCE: f64 = 2.0;
+
+/// vertical movement ignored when detecting descents and ascents expressed
+/// in feet of sea water
+pub const DEPTH_TOLERANCE: f64 = 1.0;
+
+/// phase of the dive followed by the monitor
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DivePhase {
+  /// the diver is at the surface
+  Surface,
+  /// the diver is descending to the bottom
+  Descent,
+  /// the diver is on the bottom
+  Bottom,
+  /// the diver is ascending, from the bottom to a stop or to the surface
+  /// or between the levels of the bottom
+  Ascent,
+  /// the diver is at a decompression stop
+  Stop,
+}
+
+/// events raised by the monitor while following a dive
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum MonitorEvent {
+  /// the diver left the surface
+  LeftSurface {
+    /// elapsed time of the event expressed in seconds
+    time: u32,
+  },
+  /// the bottom time exceeded the no decompression limit
+  NoDecompressionLimitExceeded {
+    /// elapsed time of the event expressed in seconds
+    time: u32,
+  },
+  /// the bottom time exceeded the air decompression tables, no schedule
+  /// covers the dive
+  OutsideDecompressionTable {
+    /// elapsed time of the event expressed in seconds
+    time: u32,
+  },
+  /// the diver left the bottom and committed to the current schedule,
+  /// raised once the ascent reaches the first stop or the surface
+  LeftBottom {
+    /// elapsed time of the event expressed in seconds
+    time: u32,
+  },
+  /// the diver ascended faster than the allowed ascent rate
+  AscentTooFast {
+    /// elapsed time of the event expressed in seconds
+    time: u32,
+    /// ascent rate expressed in feet of sea water per minute
+    rate: f64,
+  },
+  /// the diver reached a decompression stop
+  StopReached {
+    /// elapsed time of the event expressed in seconds
+    time: u32,
+    /// depth of the stop expressed in feet of sea water
+    depth: u16,
+  },
+  /// the diver completed a decompression stop
+  StopCompleted {
+    /// elapsed time of the event expressed in seconds
+    time: u32,
+    /// depth of the stop expressed in feet of sea water
+    depth: u16,
+  },
+  /// the diver ascended above a decompression stop before completing it
+  StopMissed {
+    /// elapsed time of the event expressed in seconds
+    time: u32,
+    /// depth of the stop expressed in feet of sea water
+    depth: u16,
+    /// stop time left when the diver ascended expressed in seconds
+    remaining: u32,
+  },
+  /// the diver reached the surface
+  ReachedSurface {
+    /// elapsed time of the event expressed in seconds
+    time: u32,
+  },
+}
+
+/// progress of the diver at the current decompression stop
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
+pub struct StopProgress {
+  /// depth of the stop expressed in feet of sea water
+  pub depth: u16,
+  /// time spent at the stop expressed in seconds
+  pub elapsed: u32,
+  /// stop time required by the schedule expressed in seconds
+  pub required: u32,
+}
+
+/// the live state of the dive
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct MonitorState {
+  /// elapsed time of the last sample expressed in seconds
+  pub time: u32,
+  /// depth of the last sample expressed in feet of sea water
+  pub depth: f64,
+  /// current phase of the dive
+  pub phase: DivePhase,
+  /// deepest depth reached rounded up to the next foot of sea water
+  pub max_depth: u16,
+  /// bottom time rounded up to the next minute
+  pub bottom_time: u16,
+  /// no decompression time left expressed in minutes, none once the
+  /// limit has been exceeded
+  pub remaining_no_decompression: Option<u16>,
+  /// the air decompression schedule the diver is committed to once the
+  /// bottom time exceeds the no decompression limit
+  pub schedule: Option<RowDeco>,
+  /// progress at the next decompression stop, once the diver left the bottom
+  pub next_stop: Option<StopProgress>,
+}
+
+/// the state and events resulting from a depth sample
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct MonitorUpdate {
+  /// the live state of the dive after the sample
+  pub state: MonitorState,
+  /// the events raised by the sample
+  pub events: Vec<MonitorEvent>,
+}
+
+/// a streaming monitor fed with the depth samples of a dive
+#[derive(Clone, Debug)]
+pub struct DiveMonitor {
+  state: MonitorState,
+  samples: usize,
+  leave_surface: Option<u32>,
+  leave_bottom: Option<u32>,
+  bottom: Option<(u32, f64)>,
+  stops: Vec<StopProgress>,
+  ascending_too_fast: bool,
+  at_stop: bool,
+}
+
+impl Default for DiveMonitor {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl DiveMonitor {
+  pub fn new() -> Self {
+    //! Instantiates a new DiveMonitor object waiting at the surface
+    Self {
+      state: MonitorState {
+        time: 0,
+        depth: 0.0,
+        phase: DivePhase::Surface,
+        max_depth: 0,
+        bottom_time: 0,
+        remaining_no_decompression: None,
+        schedule: None,
+        next_stop: None,
+      },
+      samples: 0,
+      leave_surface: None,
+      leave_bottom: None,
+      bottom: None,
+      stops: vec![],
+      ascending_too_fast: false,
+      at_stop: false,
+    }
+  }
+
+  pub fn state(&self) -> &MonitorState {
+    //! returns the live state of the dive
+    &self.state
+  }
+
+  pub fn update(&mut self, time: u32, depth: f64) -> Result<MonitorUpdate, DiveError> {
+    //! feeds a depth sample to the monitor and returns the live state along
+    //! with the events it raised. the time is the elapsed time expressed in
+    //! seconds and the depth is expressed in feet of sea water
+    if self.samples > 0 && time < self.state.time {
+      return Err(DiveError::UnorderedSamples { index: self.samples });
+    }
+
+    let previous = (self.state.time, self.state.depth);
+    let first = self.samples == 0;
+    self.samples += 1;
+    self.state.time = time;
+    self.state.depth = depth;
+
+    let mut events = vec![];
+    let underwater = depth > SURFACE_DEPTH;
+
+    match (self.leave_surface, underwater) {
+      (None, false) => {
+        self.state.phase = DivePhase::Surface;
+        return Ok(self.snapshot(events));
+      }
+      (None, true) => {
+        let leave_surface = if first { time } else { previous.0 };
+        self.leave_surface = Some(leave_surface);
+        self.state.phase = DivePhase::Descent;
+        events.push(MonitorEvent::LeftSurface { time: leave_surface });
+      }
+      (Some(_), false) if self.state.phase != DivePhase::Surface => {
+        if self.leave_bottom.is_none() {
+          self.commit_ascent(&mut events);
+        }
+        self.track_ascent(previous, &mut events);
+        for stop in self.stops.drain(..).filter(|stop| stop.elapsed < stop.required) {
+          events.push(MonitorEvent::StopMissed { time, depth: stop.depth, remaining: stop.required - stop.elapsed });
+        }
+        self.state.phase = DivePhase::Surface;
+        self.state.next_stop = None;
+        events.push(MonitorEvent::ReachedSurface { time });
+        return Ok(self.snapshot(events));
+      }
+      (Some(_), false) => return Ok(self.snapshot(events)),
+      (Some(_), true) => {}
+    }
+
+    self.state.max_depth = self.state.max_depth.max(fsw(depth));
+
+    if self.leave_bottom.is_some() && depth > previous.1 + DEPTH_TOLERANCE && self.stops.iter().all(|stop| stop.elapsed == 0) {
+      // the diver went back down before starting the decompression
+      self.leave_bottom = None;
+    }
+
+    match self.leave_bottom {
+      None => self.track_bottom(previous, &mut events),
+      Some(_) => {
+        self.track_ascent(previous, &mut events);
+        self.track_stops(previous, &mut events);
+      }
+    }
+
+    Ok(self.snapshot(events))
+  }
+
+  fn track_bottom(&mut self, previous: (u32, f64), events: &mut Vec<MonitorEvent>) {
+    let (time, depth) = (self.state.time, self.state.depth);
+    let leave_surface = self.leave_surface.unwrap_or(time);
+    let descending = depth > previous.1 + DEPTH_TOLERANCE;
+    let ascending = depth < previous.1 - DEPTH_TOLERANCE;
+
+    // the diver leaves the bottom at the last sample at the deepest depth
+    // reached since the last descent, as in the reduction of a profile
+    self.bottom = match self.bottom {
+      Some((_, level)) if !descending && depth >= level - DEPTH_TOLERANCE => Some((time, level.max(depth))),
+      Some(bottom) if !descending => Some(bottom),
+      _ => Some((time, depth)),
+    };
+
+    self.state.phase = if descending {
+      DivePhase::Descent
+    } else if ascending {
+      DivePhase::Ascent
+    } else {
+      DivePhase::Bottom
+    };
+
+    // the bottom clock runs until the ascent turns out to be the final one
+    self.state.bottom_time = minutes(time - leave_surface);
+    self.commit_schedule(events);
+    self.track_ascent(previous, events);
+
+    if self.stops.first().is_some_and(|stop| depth <= f64::from(stop.depth) + STOP_TOLERANCE) {
+      self.commit_ascent(events);
+      self.track_stops(previous, events);
+    }
+  }
+
+  fn commit_ascent(&mut self, events: &mut Vec<MonitorEvent>) {
+    let leave_surface = self.leave_surface.unwrap_or(self.state.time);
+    let (leave_bottom, _) = self.bottom.unwrap_or((self.state.time, self.state.depth));
+
+    self.leave_bottom = Some(leave_bottom);
+    self.state.bottom_time = minutes(leave_bottom - leave_surface);
+    self.commit_schedule(events);
+    events.push(MonitorEvent::LeftBottom { time: leave_bottom });
+  }
+
+  fn commit_schedule(&mut self, events: &mut Vec<MonitorEvent>) {
+    let dive = Dive::new(self.state.max_depth, self.state.bottom_time);
+    let ndl = dive.no_decompression_limit();
+
+    if self.state.bottom_time <= ndl && self.state.max_depth <= 190 {
+      self.state.remaining_no_decompression = Some(ndl - self.state.bottom_time);
+      self.state.schedule = None;
+      self.stops.clear();
+      return;
+    }
+
+    if self.state.remaining_no_decompression.is_some() || self.state.schedule.is_none() {
+      events.push(MonitorEvent::NoDecompressionLimitExceeded { time: self.state.time });
+    }
+    self.state.remaining_no_decompression = None;
+
+    let schedule = dive.deco_dive();
+    if schedule.max_time == 0 && !matches!(&self.state.schedule, Some(row) if row.max_time == 0) {
+      events.push(MonitorEvent::OutsideDecompressionTable { time: self.state.time });
+    }
+    let mut stops: Vec<DecoStops> = schedule.air_deco_stops.clone();
+    stops.sort_by_key(|stop| std::cmp::Reverse(stop.depth));
+    self.stops = stops.iter()
+      .map(|stop| StopProgress { depth: stop.depth, elapsed: 0, required: u32::from(stop.time) * 60 })
+      .collect();
+    self.state.schedule = Some(schedule);
+  }
+
+  fn track_ascent(&mut self, previous: (u32, f64), events: &mut Vec<MonitorEvent>) {
+    let elapsed = self.state.time - previous.0;
+    if elapsed == 0 {
+      return;
+    }
+
+    let rate = (previous.1 - self.state.depth) / f64::from(elapsed) * 60.0;
+    let too_fast = rate > ASCENT_RATE + ASCENT_RATE_TOLERANCE;
+    if too_fast && !self.ascending_too_fast {
+      events.push(MonitorEvent::AscentTooFast { time: self.state.time, rate });
+    }
+    self.ascending_too_fast = too_fast;
+  }
+
+  fn track_stops(&mut self, previous: (u32, f64), events: &mut Vec<MonitorEvent>) {
+    let (time, depth) = (self.state.time, self.state.depth);
+    self.state.phase = DivePhase::Ascent;
+
+    while let Some(stop) = self.stops.first_mut() {
+      let stop_depth = f64::from(stop.depth);
+
+      if depth < stop_depth - STOP_TOLERANCE {
+        events.push(MonitorEvent::StopMissed { time, depth: stop.depth, remaining: stop.required - stop.elapsed });
+        self.stops.remove(0);
+        self.at_stop = false;
+        continue;
+      }
+
+      if (depth - stop_depth).abs() > STOP_TOLERANCE {
+        self.at_stop = false;
+        break;
+      }
+
+      self.state.phase = DivePhase::Stop;
+      if self.at_stop {
+        stop.elapsed += time - previous.0;
+      } else {
+        self.at_stop = true;
+        events.push(MonitorEvent::StopReached { time, depth: stop.depth });
+      }
+
+      if stop.elapsed >= stop.required {
+        events.push(MonitorEvent::StopCompleted { time, depth: stop.depth });
+        self.stops.remove(0);
+        self.at_stop = false;
+        self.state.phase = DivePhase::Ascent;
+        continue;
+      }
+      break;
+    }
+
+    self.state.next_stop = self.stops.first().copied();
+  }
+
+  fn snapshot(&self, events: Vec<MonitorEvent>) -> MonitorUpdate {
+    MonitorUpdate {
+      state: self.state.clone(),
+      events,
+    }
+  }
+}
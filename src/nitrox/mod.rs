@@ -0,0 +1,149 @@
+use crate::{Dive, ExposureModifier};
+use crate::advisories::{self, Advisory, AdvisoryCode};
+use crate::airtables::RowDeco;
+use crate::errors::DiveError;
+
+/// highest oxygen partial pressure allowed on the bottom of a nitrox dive
+/// expressed in atmospheres absolute
+pub const MAX_PO2: f64 = 1.4;
+
+/// lowest oxygen fraction accepted for a nitrox mix
+pub const MIN_FO2: f64 = 0.21;
+
+/// highest oxygen fraction accepted for a nitrox mix
+pub const MAX_FO2: f64 = 0.40;
+
+/// fraction of nitrogen in air used to compute the equivalent air depth
+const AIR_FN2: f64 = 0.79;
+
+/// tolerance absorbing floating point noise before rounding depths
+const DEPTH_EPSILON: f64 = 1e-4;
+
+/// single enriched air nitrox dive object
+#[derive(Serialize, Deserialize, Copy, Clone, Debug)]
+pub struct NitroxDive {
+  /// actual depth of the dive expressed in feet of sea water
+  pub depth: u16,
+  /// bottom time of the dive expressed in minutes
+  pub bottom_time: u16,
+  /// fraction of oxygen in the breathing mix (e.g 0.32 for EANx 32)
+  pub fo2: f64,
+  /// conditions of the dive that require the next longer bottom time schedule
+  #[serde(default)]
+  pub exposure: ExposureModifier,
+}
+
+/// the air table schedule and oxygen exposure of a nitrox dive
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct NitroxPlan {
+  /// equivalent air depth expressed in feet of sea water
+  pub equivalent_air_depth: u16,
+  /// maximum operating depth of the mix expressed in feet of sea water
+  pub maximum_operating_depth: u16,
+  /// the air dive at the equivalent air depth used to look up the tables
+  pub air_dive: Dive,
+  /// no decompression limit at the equivalent air depth expressed in minutes
+  pub no_decompression_limit: u16,
+  /// group letter of the dive at the equivalent air depth
+  pub group_letter: String,
+  /// the air decompression schedule, only when the bottom time exceeds
+  /// the no decompression limit
+  pub deco: Option<RowDeco>,
+  /// oxygen partial pressure on the bottom expressed in atmospheres absolute
+  pub po2: f64,
+  /// time spent breathing the mix at the bottom expressed in minutes
+  pub o2_exposure_time: u16,
+  /// the advisories relevant to the dive
+  pub advisories: Vec<Advisory>,
+}
+
+impl NitroxDive {
+  pub fn new(depth: u16, bottom_time: u16, fo2: f64) -> Self {
+    //! Instantiates a new NitroxDive object
+    //! the depth is expressed in feet of sea water
+    //! the bottom_time is expressed in minutes
+    //! the fo2 is the fraction of oxygen in the mix
+    Self {
+      depth,
+      bottom_time,
+      fo2,
+      exposure: ExposureModifier::None,
+    }
+  }
+
+  pub fn with_exposure(self, exposure: ExposureModifier) -> Self {
+    //! returns the NitroxDive object with the given exposure modifier
+    Self {
+      exposure,
+      ..self
+    }
+  }
+
+  pub fn po2(self) -> f64 {
+    //! returns the oxygen partial pressure at the bottom of the dive
+    //! expressed in atmospheres absolute
+    self.fo2 * (f64::from(self.depth) + 33.0) / 33.0
+  }
+
+  pub fn equivalent_air_depth(self) -> u16 {
+    //! returns the equivalent air depth of the dive rounded up to the next
+    //! foot of sea water
+    let ead = (1.0 - self.fo2) * (f64::from(self.depth) + 33.0) / AIR_FN2 - 33.0;
+    (ead - DEPTH_EPSILON).ceil().max(0.0) as u16
+  }
+
+  pub fn maximum_operating_depth(self) -> u16 {
+    //! returns the deepest depth allowed for the mix, rounded down to the
+    //! foot of sea water, where the oxygen partial pressure reaches MAX_PO2
+    let mod_fsw = MAX_PO2 / self.fo2 * 33.0 - 33.0;
+    (mod_fsw + DEPTH_EPSILON).floor().max(0.0) as u16
+  }
+
+  pub fn air_dive(self) -> Dive {
+    //! returns the air Dive object at the equivalent air depth
+    Dive::new(self.equivalent_air_depth(), self.bottom_time).with_exposure(self.exposure)
+  }
+
+  pub fn plan(self) -> Result<NitroxPlan, DiveError> {
+    //! returns the air table schedule at the equivalent air depth along with
+    //! the oxygen exposure of the dive. the mix must be within MIN_FO2 and
+    //! MAX_FO2 and the depth must not exceed the maximum operating depth
+    if !(MIN_FO2..=MAX_FO2).contains(&self.fo2) {
+      return Err(DiveError::InvalidOxygenFraction { fo2: self.fo2 });
+    }
+
+    let maximum_operating_depth = self.maximum_operating_depth();
+    if self.depth > maximum_operating_depth {
+      return Err(DiveError::MaximumOperatingDepthExceeded {
+        depth: self.depth,
+        maximum_operating_depth,
+      });
+    }
+
+    let air_dive = self.air_dive();
+    let no_decompression = air_dive.advised_group_letter();
+
+    // the scuba limit applies to the actual depth rather than the equivalent air depth
+    let mut advisories = advisories::depth_advisories(self.depth);
+    let (deco, schedule_advisories) = if air_dive.requires_decompression() {
+      let deco = air_dive.advised_deco_dive();
+      (Some(deco.value), deco.advisories)
+    } else {
+      (None, no_decompression.advisories)
+    };
+    advisories.extend(schedule_advisories.into_iter()
+      .filter(|advisory| advisory.code != AdvisoryCode::DepthExceedsScubaLimit));
+
+    Ok(NitroxPlan {
+      equivalent_air_depth: air_dive.depth,
+      maximum_operating_depth,
+      air_dive,
+      no_decompression_limit: air_dive.no_decompression_limit(),
+      group_letter: no_decompression.value,
+      deco,
+      po2: self.po2(),
+      o2_exposure_time: self.bottom_time,
+      advisories,
+    })
+  }
+}
@@ -0,0 +1,238 @@
+use crate::advisories::{Advisory, AdvisoryCode};
+use crate::airtables::{DecoStops, RowDeco};
+use crate::contingencies::O2_STOP_MAX_DEPTH;
+use crate::nitrox::NitroxPlan;
+
+/// single exposure CNS oxygen toxicity limits from the NOAA diving manual
+/// as (oxygen partial pressure in ata, limit in minutes)
+const CNS_LIMITS: [(f64, f64); 11] = [
+  (0.6, 720.0),
+  (0.7, 570.0),
+  (0.8, 450.0),
+  (0.9, 360.0),
+  (1.0, 300.0),
+  (1.1, 240.0),
+  (1.2, 210.0),
+  (1.3, 180.0),
+  (1.4, 150.0),
+  (1.5, 120.0),
+  (1.6, 45.0),
+];
+
+/// oxygen partial pressure below which no oxygen exposure is accounted
+/// expressed in atmospheres absolute
+pub const PO2_THRESHOLD: f64 = 0.5;
+
+/// highest oxygen partial pressure covered by the NOAA CNS limits expressed
+/// in atmospheres absolute. oxygen breathed above it, such as the in-water
+/// oxygen stops and the SurDO2 chamber periods, follows the oxygen limits
+/// of the US Navy procedures instead
+pub const CNS_MAX_PO2: f64 = 1.6;
+
+/// margin within which a partial pressure still falls in the highest row of
+/// the NOAA limits expressed in atmospheres absolute. oxygen breathed at the
+/// 20 fsw stop is 53 / 33 = 1.606 ata, the 1.6 ata of the US Navy procedures
+pub const CNS_MAX_PO2_MARGIN: f64 = 0.01;
+
+/// highest CNS oxygen toxicity allowed across a dive day expressed in percent
+pub const CNS_LIMIT: f64 = 100.0;
+
+/// highest pulmonary oxygen toxicity units allowed across a dive day
+pub const DAILY_OTU_LIMIT: f64 = 850.0;
+
+/// half time of the CNS oxygen toxicity elimination during surface
+/// intervals expressed in minutes
+pub const CNS_HALF_TIME: f64 = 90.0;
+
+/// a period of time breathing a constant oxygen partial pressure
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
+pub struct OxygenSegment {
+  /// oxygen partial pressure expressed in atmospheres absolute
+  pub po2: f64,
+  /// duration of the segment expressed in minutes
+  pub minutes: u16,
+}
+
+/// the oxygen exposure accumulated across the segments of a dive day
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct OxygenExposure {
+  /// the segments added to the accumulator in chronological order
+  pub segments: Vec<OxygenSegment>,
+  /// CNS oxygen toxicity expressed in percent, reduced by surface intervals
+  pub cns: f64,
+  /// pulmonary oxygen toxicity units accumulated across the day
+  pub otu: f64,
+  /// minutes breathed above CNS_MAX_PO2, outside the range of the NOAA
+  /// limits and therefore not accounted in the CNS oxygen toxicity
+  #[serde(default)]
+  pub out_of_range_minutes: u16,
+}
+
+impl OxygenSegment {
+  pub fn new(po2: f64, minutes: u16) -> Self {
+    //! Instantiates a new OxygenSegment object
+    //! the po2 is expressed in atmospheres absolute
+    //! the minutes are the duration of the segment
+    Self {
+      po2,
+      minutes,
+    }
+  }
+
+  pub fn at_depth(depth: u16, fo2: f64, minutes: u16) -> Self {
+    //! Instantiates a new OxygenSegment object breathing a mix with the
+    //! given oxygen fraction at a depth expressed in feet of sea water
+    Self::new(fo2 * (f64::from(depth) + 33.0) / 33.0, minutes)
+  }
+
+  pub fn cns(self) -> f64 {
+    //! returns the CNS oxygen toxicity of the segment expressed in percent.
+    //! partial pressures between two rows of the NOAA limits use the limit
+    //! of the higher row. partial pressures above CNS_MAX_PO2 are outside
+    //! the NOAA limits and return 0
+    if self.po2 <= PO2_THRESHOLD || !self.within_cns_limits() {
+      return 0.0;
+    }
+
+    let limit = CNS_LIMITS.iter()
+      .find(|(po2, _)| self.po2 <= *po2 + 1e-9)
+      .unwrap_or(&CNS_LIMITS[CNS_LIMITS.len() - 1])
+      .1;
+
+    f64::from(self.minutes) / limit * 100.0
+  }
+
+  pub fn within_cns_limits(self) -> bool {
+    //! returns true when the partial pressure is covered by the NOAA limits,
+    //! within CNS_MAX_PO2_MARGIN of the highest row
+    self.po2 <= CNS_MAX_PO2 + CNS_MAX_PO2_MARGIN
+  }
+
+  pub fn otu(self) -> f64 {
+    //! returns the pulmonary oxygen toxicity units of the segment
+    if self.po2 <= PO2_THRESHOLD {
+      return 0.0;
+    }
+
+    f64::from(self.minutes) * ((self.po2 - PO2_THRESHOLD) / 0.5).powf(0.83)
+  }
+}
+
+/// in_water_o2_stops() returns the stops of a decompression schedule at
+/// which oxygen is breathed in the water
+pub fn in_water_o2_stops(schedule: &RowDeco) -> Vec<DecoStops> {
+  schedule.o2_deco_stops.iter()
+    .filter(|stop| stop.depth <= O2_STOP_MAX_DEPTH && stop.time > 0)
+    .cloned()
+    .collect()
+}
+
+/// chamber_stops() returns the oxygen breathing periods of a SurDO2 session
+/// with the given number of chamber periods. the first period is breathed
+/// for 15 minutes at 50 fsw and 15 minutes at 40 fsw, periods 2 to 4 at
+/// 40 fsw and any further period at 30 fsw, every period (or half period)
+/// lasting 30 (or 15) minutes
+pub fn chamber_stops(o2cp: f32) -> Vec<DecoStops> {
+  let halves = (o2cp * 2.0).round() as u16;
+
+  (0..halves)
+    .map(|half| DecoStops {
+      depth: match half {
+        0 => 50,
+        1..=7 => 40,
+        _ => 30,
+      },
+      time: 15,
+    })
+    .collect()
+}
+
+/// tender_o2_minutes() returns the oxygen breathed by the inside tender at
+/// the end of a SurDO2 session with the given number of chamber periods
+/// expressed in minutes: 15 minutes up to 1.5 periods and 30 minutes for
+/// longer sessions
+pub fn tender_o2_minutes(o2cp: f32) -> u16 {
+  if o2cp <= 1.5 {
+    15
+  } else {
+    30
+  }
+}
+
+/// o2_stop_segments() returns the oxygen breathing segments of the in-water
+/// oxygen stops of a decompression schedule
+pub fn o2_stop_segments(schedule: &RowDeco) -> Vec<OxygenSegment> {
+  in_water_o2_stops(schedule).iter()
+    .map(|stop| OxygenSegment::at_depth(stop.depth, 1.0, stop.time))
+    .collect()
+}
+
+/// chamber_segments() returns the oxygen breathing segments of a SurDO2
+/// session with the given number of chamber periods
+pub fn chamber_segments(o2cp: f32) -> Vec<OxygenSegment> {
+  chamber_stops(o2cp).iter()
+    .map(|stop| OxygenSegment::at_depth(stop.depth, 1.0, stop.time))
+    .collect()
+}
+
+impl OxygenExposure {
+  pub fn new() -> Self {
+    //! Instantiates a new empty OxygenExposure accumulator
+    Self::default()
+  }
+
+  pub fn add_segment(&mut self, segment: OxygenSegment) {
+    //! adds a segment to the accumulated oxygen exposure
+    if !segment.within_cns_limits() {
+      self.out_of_range_minutes = self.out_of_range_minutes.saturating_add(segment.minutes);
+    }
+    self.cns += segment.cns();
+    self.otu += segment.otu();
+    self.segments.push(segment);
+  }
+
+  pub fn add_o2_stops(&mut self, schedule: &RowDeco) {
+    //! adds the in-water oxygen stops of a decompression schedule
+    for segment in o2_stop_segments(schedule) {
+      self.add_segment(segment);
+    }
+  }
+
+  pub fn add_chamber_periods(&mut self, o2cp: f32) {
+    //! adds the oxygen periods of a SurDO2 chamber session
+    for segment in chamber_segments(o2cp) {
+      self.add_segment(segment);
+    }
+  }
+
+  pub fn add_nitrox(&mut self, plan: &NitroxPlan) {
+    //! adds the bottom phase of a nitrox dive
+    self.add_segment(OxygenSegment::new(plan.po2, plan.o2_exposure_time));
+  }
+
+  pub fn add_surface_interval(&mut self, minutes: u16) {
+    //! reduces the CNS oxygen toxicity by the elimination during a surface
+    //! interval. pulmonary oxygen toxicity units are not reduced within
+    //! the dive day
+    self.cns *= 0.5_f64.powf(f64::from(minutes) / CNS_HALF_TIME);
+  }
+
+  pub fn advisories(&self) -> Vec<Advisory> {
+    //! returns the advisories raised when the accumulated exposure exceeds
+    //! the CNS or daily OTU limits, or holds oxygen breathed outside the
+    //! range of the CNS limits
+    let mut advisories = vec![];
+
+    if self.cns > CNS_LIMIT {
+      advisories.push(Advisory::new(AdvisoryCode::CnsLimitExceeded));
+    }
+    if self.otu > DAILY_OTU_LIMIT {
+      advisories.push(Advisory::new(AdvisoryCode::OtuLimitExceeded));
+    }
+    if self.out_of_range_minutes > 0 {
+      advisories.push(Advisory::new(AdvisoryCode::OxygenOutsideCnsLimits));
+    }
+
+    advisories
+  }
+}
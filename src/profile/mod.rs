@@ -0,0 +1,210 @@
+use crate::Dive;
+use crate::advisories::Advised;
+use crate::airtables::{DecoStops, RowDeco};
+use crate::errors::DiveError;
+use crate::gas::{ASCENT_RATE, DESCENT_RATE};
+use crate::monitor::DEPTH_TOLERANCE;
+
+/// depth at or above which the diver is considered at the surface
+/// expressed in feet of sea water
+pub const SURFACE_DEPTH: f64 = 1.0;
+
+/// a depth sample of a dive profile
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
+pub struct ProfileSample {
+  /// elapsed time since the beginning of the profile expressed in seconds
+  pub time: u32,
+  /// depth of the diver expressed in feet of sea water
+  pub depth: f64,
+}
+
+/// a segment of a dive profile moving to a depth in a given time
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
+pub struct ProfileSegment {
+  /// depth at the end of the segment expressed in feet of sea water
+  pub depth: f64,
+  /// duration of the segment expressed in seconds
+  pub duration: u32,
+}
+
+/// a variable depth dive profile
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DiveProfile {
+  /// the depth samples of the profile in chronological order
+  pub samples: Vec<ProfileSample>,
+}
+
+/// the table inputs derived from a dive profile
+#[derive(Serialize, Deserialize, Copy, Clone, Debug)]
+pub struct ProfileReduction {
+  /// deepest depth of the profile rounded up to the next foot of sea water
+  pub max_depth: u16,
+  /// elapsed time when the diver left the surface expressed in seconds
+  pub leave_surface: u32,
+  /// elapsed time when the diver left the bottom, the beginning of the
+  /// final ascent, expressed in seconds
+  pub leave_bottom: u32,
+  /// elapsed time when the diver reached the surface expressed in seconds,
+  /// none when the profile ends underwater
+  pub reach_surface: Option<u32>,
+  /// time from leaving the surface to leaving the bottom rounded up to the
+  /// next minute
+  pub bottom_time: u16,
+  /// the table dive made of the max_depth and bottom_time
+  pub dive: Dive,
+}
+
+/// the table results of a dive profile
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ProfileEvaluation {
+  /// the table inputs derived from the profile
+  pub reduction: ProfileReduction,
+  /// no decompression limit at the max_depth expressed in minutes
+  pub no_decompression_limit: u16,
+  /// group letter of the reduced dive along with its advisories
+  pub group_letter: Advised<String>,
+  /// the air decompression schedule along with its advisories, only when
+  /// the bottom time exceeds the no decompression limit
+  pub deco: Option<Advised<RowDeco>>,
+}
+
+impl DiveProfile {
+  pub fn from_samples(samples: Vec<ProfileSample>) -> Result<Self, DiveError> {
+    //! Instantiates a new DiveProfile object from depth samples. the
+    //! samples must be in chronological order
+    if samples.is_empty() {
+      return Err(DiveError::EmptyProfile);
+    }
+    if let Some(index) = samples.windows(2).position(|pair| pair[1].time < pair[0].time) {
+      return Err(DiveError::UnorderedSamples { index: index + 1 });
+    }
+
+    Ok(Self {
+      samples,
+    })
+  }
+
+  pub fn from_segments(segments: &[ProfileSegment]) -> Result<Self, DiveError> {
+    //! Instantiates a new DiveProfile object starting at the surface and
+    //! following the given segments
+    let mut samples = vec![ProfileSample { time: 0, depth: 0.0 }];
+    let mut time = 0;
+
+    for segment in segments.iter() {
+      time += segment.duration;
+      samples.push(ProfileSample { time, depth: segment.depth });
+    }
+
+    Self::from_samples(samples)
+  }
+
+  pub fn planned(dive: Dive, stops: &[DecoStops]) -> Self {
+    //! Instantiates the planned DiveProfile object of a table dive: a
+    //! descent at DESCENT_RATE, the bottom until the bottom time has
+    //! elapsed, then an ascent at ASCENT_RATE holding every stop for its
+    //! full stop time, deepest first
+    let depth = f64::from(dive.depth);
+    let bottom_time = u32::from(dive.bottom_time) * 60;
+    let descent = ((depth / DESCENT_RATE * 60.0).round() as u32).min(bottom_time);
+
+    let mut samples = vec![
+      ProfileSample { time: 0, depth: 0.0 },
+      ProfileSample { time: descent, depth },
+    ];
+    if bottom_time > descent {
+      samples.push(ProfileSample { time: bottom_time, depth });
+    }
+
+    let mut stops = stops.to_vec();
+    stops.sort_by_key(|stop| std::cmp::Reverse(stop.depth));
+
+    let (mut time, mut current) = (bottom_time, depth);
+    for stop in stops.iter().filter(|stop| f64::from(stop.depth) < depth) {
+      let stop_depth = f64::from(stop.depth);
+      time += ((current - stop_depth) / ASCENT_RATE * 60.0).round() as u32;
+      samples.push(ProfileSample { time, depth: stop_depth });
+      time += u32::from(stop.time) * 60;
+      samples.push(ProfileSample { time, depth: stop_depth });
+      current = stop_depth;
+    }
+    time += (current / ASCENT_RATE * 60.0).round() as u32;
+    samples.push(ProfileSample { time, depth: 0.0 });
+
+    Self {
+      samples,
+    }
+  }
+
+  pub fn reduce(&self) -> Result<ProfileReduction, DiveError> {
+    //! returns the table inputs of the profile: the deepest depth and the
+    //! bottom time from leaving the surface to leaving the bottom. the
+    //! diver leaves the bottom at the last sample at the deepest depth of
+    //! the final ascent, the part of the profile after which the diver
+    //! never descends again by more than DEPTH_TOLERANCE
+    let samples = &self.samples;
+    let first_dive = samples.iter()
+      .position(|sample| sample.depth > SURFACE_DEPTH)
+      .ok_or(DiveError::EmptyProfile)?;
+    let leave_surface = samples[first_dive.saturating_sub(1)].time;
+
+    // walking back from the end, the final ascent goes on as long as the
+    // diver was never shallower than its deepest depth so far, within the
+    // tolerance of the small depth changes held at a level
+    let mut ascent_start = samples.len() - 1;
+    let mut bottom_depth = samples[ascent_start].depth;
+    while ascent_start > first_dive && samples[ascent_start - 1].depth >= bottom_depth - DEPTH_TOLERANCE {
+      ascent_start -= 1;
+      bottom_depth = bottom_depth.max(samples[ascent_start].depth);
+    }
+    let leave_bottom = samples[ascent_start..].iter()
+      .take_while(|sample| sample.depth >= bottom_depth - DEPTH_TOLERANCE)
+      .last()
+      .map_or(samples[ascent_start].time, |sample| sample.time);
+
+    let reach_surface = samples[ascent_start..].iter()
+      .find(|sample| sample.depth <= SURFACE_DEPTH)
+      .map(|sample| sample.time);
+
+    let max_depth = samples.iter().map(|sample| sample.depth).fold(0.0, f64::max);
+    let max_depth = fsw(max_depth);
+    let bottom_time = minutes(leave_bottom - leave_surface);
+
+    Ok(ProfileReduction {
+      max_depth,
+      leave_surface,
+      leave_bottom,
+      reach_surface,
+      bottom_time,
+      dive: Dive::new(max_depth, bottom_time),
+    })
+  }
+
+  pub fn evaluate(&self) -> Result<ProfileEvaluation, DiveError> {
+    //! returns the no decompression limit, the group letter and, when
+    //! needed, the air decompression schedule of the reduced profile
+    let reduction = self.reduce()?;
+    let group_letter = reduction.dive.advised_group_letter();
+    let deco = if reduction.dive.requires_decompression() {
+      Some(reduction.dive.advised_deco_dive())
+    } else {
+      None
+    };
+
+    Ok(ProfileEvaluation {
+      no_decompression_limit: reduction.dive.no_decompression_limit(),
+      reduction,
+      group_letter,
+      deco,
+    })
+  }
+}
+
+/// fsw() rounds a depth up to the next foot of sea water
+pub fn fsw(depth: f64) -> u16 {
+  (depth - 1e-6).ceil().max(0.0) as u16
+}
+
+/// minutes() rounds a duration expressed in seconds up to the next minute
+pub fn minutes(seconds: u32) -> u16 {
+  seconds.div_ceil(60) as u16
+}
@@ -0,0 +1,288 @@
+use std::fmt;
+
+use crate::{Dive, DivePlan};
+use crate::airtables::{DecoStops, RowDeco};
+use crate::errors::DiveError;
+use crate::profile::minutes;
+
+/// the events the timekeeper records for a diver
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StationEvent {
+  /// the diver left the surface (LS)
+  LeftSurface,
+  /// the diver reached the bottom (RB)
+  ReachedBottom,
+  /// the diver left the bottom (LB)
+  LeftBottom,
+  /// the diver reached the next decompression stop
+  ReachedStop,
+  /// the diver left the current decompression stop
+  LeftStop,
+  /// the diver reached the surface (RS)
+  ReachedSurface,
+}
+
+/// where the diver is, as known to the timekeeper
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum DiverStatus {
+  /// the diver has not dived yet
+  Predive,
+  /// the diver is descending to the bottom
+  Descent,
+  /// the diver is on the bottom
+  Bottom,
+  /// the diver is travelling to a stop or to the surface
+  Ascent,
+  /// the diver is at a decompression stop
+  Stop {
+    /// depth of the stop expressed in feet of sea water
+    depth: u16,
+  },
+  /// the diver is on the surface after a dive
+  SurfaceInterval,
+}
+
+/// the countdown of a decompression stop
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct StopCountdown {
+  /// depth of the stop expressed in feet of sea water
+  pub depth: u16,
+  /// stop time left expressed in seconds
+  pub remaining: u32,
+  /// true while the diver is at the stop
+  pub current: bool,
+}
+
+/// what the station displays for a diver
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DiverView {
+  /// name of the diver
+  pub name: String,
+  /// where the diver is
+  pub status: DiverStatus,
+  /// planned depth of the dive expressed in feet of sea water
+  pub depth: u16,
+  /// seconds since the diver left the surface, or since the diver reached
+  /// the surface during a surface interval
+  pub timer: u32,
+  /// no decompression time left expressed in minutes, negative once the
+  /// limit has been exceeded. only while the diver is on the bottom
+  pub remaining_no_decompression: Option<i32>,
+  /// the stops of the committed schedule not completed yet
+  pub stops: Vec<StopCountdown>,
+  /// repetitive group letter, ticking down during the surface interval
+  pub group_letter: Option<String>,
+}
+
+/// a diver followed by the dive station
+#[derive(Clone, Debug)]
+pub struct Diver {
+  name: String,
+  depth: u16,
+  status: DiverStatus,
+  left_surface: u32,
+  left_bottom: u32,
+  reached_stop: u32,
+  reached_surface: u32,
+  residual_nitrogen_time: u16,
+  bottom_time: u16,
+  schedule: Option<RowDeco>,
+  stops: Vec<DecoStops>,
+  last_dive: Option<Dive>,
+}
+
+impl fmt::Display for DiverStatus {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      DiverStatus::Predive => write!(f, "predive"),
+      DiverStatus::Descent => write!(f, "descent"),
+      DiverStatus::Bottom => write!(f, "bottom"),
+      DiverStatus::Ascent => write!(f, "ascent"),
+      DiverStatus::Stop { depth } => write!(f, "stop {} fsw", depth),
+      DiverStatus::SurfaceInterval => write!(f, "surface interval"),
+    }
+  }
+}
+
+impl Diver {
+  pub fn new(name: &str, depth: u16) -> Self {
+    //! Instantiates a new Diver object planned for a depth expressed in
+    //! feet of sea water
+    Self {
+      name: String::from(name),
+      depth,
+      status: DiverStatus::Predive,
+      left_surface: 0,
+      left_bottom: 0,
+      reached_stop: 0,
+      reached_surface: 0,
+      residual_nitrogen_time: 0,
+      bottom_time: 0,
+      schedule: None,
+      stops: vec![],
+      last_dive: None,
+    }
+  }
+
+  pub fn status(&self) -> DiverStatus {
+    //! returns where the diver is
+    self.status
+  }
+
+  pub fn schedule(&self) -> Option<&RowDeco> {
+    //! returns the air decompression schedule committed when the diver
+    //! left the bottom, if the dive required decompression
+    self.schedule.as_ref()
+  }
+
+  pub fn set_depth(&mut self, depth: u16) -> Result<(), DiveError> {
+    //! changes the depth of the dive expressed in feet of sea water. the
+    //! depth can no longer change once the diver left the bottom, and the
+    //! residual nitrogen time of a repetitive dive under way follows the
+    //! new depth
+    if depth == 0 {
+      return Err(DiveError::InvalidDepth { depth });
+    }
+
+    match self.status {
+      DiverStatus::Ascent | DiverStatus::Stop { .. } => Err(DiveError::UnexpectedEvent {
+        event: String::from("depth change"),
+        status: self.status.to_string(),
+      }),
+      DiverStatus::Descent | DiverStatus::Bottom => {
+        self.depth = depth;
+        self.residual_nitrogen_time = self.surface_plan(self.left_surface).map_or(0, |plan| plan.residual_nitrogen_time());
+        Ok(())
+      }
+      _ => {
+        self.depth = depth;
+        Ok(())
+      }
+    }
+  }
+
+  pub fn handle(&mut self, event: StationEvent, now: u32) -> Result<(), DiveError> {
+    //! records an event of the diver at `now`, the station clock expressed
+    //! in seconds
+    let unexpected = || DiveError::UnexpectedEvent {
+      event: format!("{:?}", event),
+      status: self.status.to_string(),
+    };
+
+    match (event, self.status) {
+      (StationEvent::LeftSurface, DiverStatus::Predive) => {
+        self.residual_nitrogen_time = 0;
+      }
+      (StationEvent::LeftSurface, DiverStatus::SurfaceInterval) => {
+        self.residual_nitrogen_time = self.surface_plan(now).map_or(0, |plan| plan.residual_nitrogen_time());
+      }
+      (StationEvent::ReachedBottom, DiverStatus::Descent) => {}
+      (StationEvent::LeftBottom, DiverStatus::Descent) | (StationEvent::LeftBottom, DiverStatus::Bottom) => {}
+      (StationEvent::ReachedStop, DiverStatus::Ascent) if !self.stops.is_empty() => {}
+      (StationEvent::LeftStop, DiverStatus::Stop { .. }) => {}
+      (StationEvent::ReachedSurface, DiverStatus::Ascent) | (StationEvent::ReachedSurface, DiverStatus::Stop { .. }) => {}
+      _ => return Err(unexpected()),
+    }
+
+    match event {
+      StationEvent::LeftSurface => {
+        self.left_surface = now;
+        self.schedule = None;
+        self.stops.clear();
+        self.status = DiverStatus::Descent;
+      }
+      StationEvent::ReachedBottom => self.status = DiverStatus::Bottom,
+      StationEvent::LeftBottom => {
+        self.left_bottom = now;
+        self.bottom_time = minutes(now.saturating_sub(self.left_surface));
+        let dive = self.equivalent_dive(self.bottom_time);
+        if dive.requires_decompression() {
+          let schedule = dive.deco_dive();
+          self.stops = schedule.air_deco_stops.clone();
+          self.stops.sort_by_key(|stop| std::cmp::Reverse(stop.depth));
+          self.schedule = Some(schedule);
+        }
+        self.status = DiverStatus::Ascent;
+      }
+      StationEvent::ReachedStop => {
+        self.reached_stop = now;
+        self.status = DiverStatus::Stop { depth: self.stops[0].depth };
+      }
+      StationEvent::LeftStop => {
+        self.stops.remove(0);
+        self.status = DiverStatus::Ascent;
+      }
+      StationEvent::ReachedSurface => {
+        self.reached_surface = now;
+        self.last_dive = Some(self.equivalent_dive(self.bottom_time));
+        self.stops.clear();
+        self.status = DiverStatus::SurfaceInterval;
+      }
+    }
+
+    Ok(())
+  }
+
+  pub fn view(&self, now: u32) -> DiverView {
+    //! returns what the station displays for the diver at `now`, the
+    //! station clock expressed in seconds
+    let timer = match self.status {
+      DiverStatus::Predive => 0,
+      DiverStatus::SurfaceInterval => now.saturating_sub(self.reached_surface),
+      _ => now.saturating_sub(self.left_surface),
+    };
+
+    let remaining_no_decompression = match self.status {
+      DiverStatus::Descent | DiverStatus::Bottom => {
+        let dive = self.equivalent_dive(minutes(timer));
+        Some(i32::from(dive.no_decompression_limit()) - i32::from(dive.bottom_time))
+      }
+      _ => None,
+    };
+
+    let stops = self.stops.iter().enumerate()
+      .map(|(index, stop)| {
+        let required = u32::from(stop.time) * 60;
+        let current = index == 0 && matches!(self.status, DiverStatus::Stop { .. });
+        let elapsed = if current { now.saturating_sub(self.reached_stop) } else { 0 };
+        StopCountdown { depth: stop.depth, remaining: required.saturating_sub(elapsed), current }
+      })
+      .collect();
+
+    let group_letter = match self.status {
+      DiverStatus::SurfaceInterval => self.surface_plan(now).map(|plan| plan.repet_letter()),
+      DiverStatus::Ascent | DiverStatus::Stop { .. } => Some(match &self.schedule {
+        Some(schedule) => schedule.repetgroup_letter.clone(),
+        None => self.equivalent_dive(self.bottom_time).group_letter(),
+      }),
+      _ => None,
+    };
+
+    DiverView {
+      name: self.name.clone(),
+      status: self.status,
+      depth: self.depth,
+      timer,
+      remaining_no_decompression,
+      stops,
+      group_letter,
+    }
+  }
+
+  /// returns the single dive the current dive counts as, the residual
+  /// nitrogen time of a repetitive dive added to its bottom time
+  fn equivalent_dive(&self, bottom_time: u16) -> Dive {
+    Dive::new(self.depth, bottom_time.saturating_add(self.residual_nitrogen_time))
+  }
+
+  /// returns the dive plan of the last dive followed by the surface
+  /// interval elapsed at `now` and a dive at the planned depth. the plan
+  /// enters the repetitive dive tables with the group of the committed
+  /// decompression schedule when the last dive required one
+  fn surface_plan(&self, now: u32) -> Option<DivePlan> {
+    let surface_interval = (now.saturating_sub(self.reached_surface) / 60) as u16;
+    self.last_dive.map(|dive| DivePlan::from_dive(dive, surface_interval, self.depth))
+  }
+}
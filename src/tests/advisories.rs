@@ -0,0 +1,63 @@
+use crate::{Dive, DivePlan};
+use crate::advisories::{AdvisoryCode, Severity};
+
+#[test]
+fn advised_no_decompression_dive() {
+  let ndl = Dive::new(60, 40).advised_no_decompression_limit();
+  assert_eq!(ndl.value, 63);
+  assert!(ndl.advisories.is_empty());
+  assert_eq!(ndl.max_severity(), None);
+}
+
+#[test]
+fn advised_group_letter_beyond_limits() {
+  let gl = Dive::new(140, 30).advised_group_letter();
+  assert!(gl.has(AdvisoryCode::DepthExceedsScubaLimit));
+  assert!(gl.has(AdvisoryCode::NoDecompressionLimitExceeded));
+  assert_eq!(gl.max_severity(), Some(Severity::Caution));
+
+  // surface-supplied dives below the scuba limit are not a warning
+  let ndl = Dive::new(150, 5).advised_no_decompression_limit();
+  assert!(ndl.has(AdvisoryCode::DepthExceedsScubaLimit));
+  assert_eq!(ndl.max_severity(), Some(Severity::Caution));
+
+  let gl = Dive::new(200, 5).advised_group_letter();
+  assert!(gl.has(AdvisoryCode::OutsideNoDecompressionTable));
+}
+
+#[test]
+fn advised_deco_dive_flags() {
+  let deco = Dive::new(100, 100).advised_deco_dive();
+  assert!(deco.has(AdvisoryCode::ExceptionalExposure));
+  assert!(deco.has(AdvisoryCode::SurDO2Required));
+  assert!(deco.has(AdvisoryCode::RepetitiveDiveNotPermitted));
+  assert!(!deco.has(AdvisoryCode::InWaterAirNotPermitted));
+
+  let deco = Dive::new(100, 150).advised_deco_dive();
+  assert!(deco.has(AdvisoryCode::InWaterAirNotPermitted));
+  assert!(!deco.has(AdvisoryCode::SurDO2Required));
+}
+
+#[test]
+fn advised_deco_dive_within_no_decompression_limit() {
+  let deco = Dive::new(60, 40).advised_deco_dive();
+  assert!(deco.has(AdvisoryCode::NoDecompressionRequired));
+  assert!(!deco.has(AdvisoryCode::OutsideDecompressionTable));
+  assert_eq!(deco.max_severity(), Some(Severity::Info));
+  assert!(!Dive::new(60, 40).requires_decompression());
+
+  let deco = Dive::new(100, 500).advised_deco_dive();
+  assert!(deco.has(AdvisoryCode::OutsideDecompressionTable));
+  assert!(!deco.has(AdvisoryCode::NoDecompressionRequired));
+  assert!(Dive::new(195, 10).requires_decompression());
+}
+
+#[test]
+fn advised_repetitive_plan() {
+  let rnt = DivePlan::new(60, 40, 120, 50).advised_residual_nitrogen_time();
+  assert!(rnt.advisories.is_empty());
+
+  let rnt = DivePlan::new(60, 40, 120, 200).advised_residual_nitrogen_time();
+  assert!(rnt.has(AdvisoryCode::OutsideRepetitiveTables));
+  assert!(rnt.has(AdvisoryCode::DepthExceedsScubaLimit));
+}
@@ -0,0 +1,74 @@
+use crate::api::{route, STATUS_BAD_REQUEST, STATUS_METHOD_NOT_ALLOWED, STATUS_NOT_FOUND, STATUS_OK, STATUS_UNPROCESSABLE_ENTITY};
+
+#[test]
+fn api_health_and_description() {
+  let response = route("GET", "/health", "");
+  assert_eq!(response.status, STATUS_OK);
+  assert_eq!(response.body["status"], "ok");
+
+  let response = route("GET", "/endpoints?format=json", "");
+  assert_eq!(response.status, STATUS_OK);
+  let paths: Vec<&str> = response.body["endpoints"].as_array().unwrap().iter()
+    .map(|endpoint| endpoint["path"].as_str().unwrap())
+    .collect();
+  assert_eq!(paths, vec!["/health", "/endpoints", "/dive", "/dive-plan", "/deco"]);
+  assert_eq!(response.body["endpoints"][2]["request"][0]["name"], "depth");
+}
+
+#[test]
+fn api_dive_results() {
+  let response = route("POST", "/dive", r#"{"depth": 60, "bottom_time": 40}"#);
+  assert_eq!(response.status, STATUS_OK);
+  assert_eq!(response.body["no_decompression_limit"]["value"], 63);
+  assert_eq!(response.body["group_letter"]["value"], "H");
+
+  let response = route("POST", "/dive-plan", r#"{"depth": 60, "bottom_time": 40, "surface_interval_time": 90, "next_dive_depth": 50}"#);
+  assert_eq!(response.status, STATUS_OK);
+  assert_eq!(response.body["repet_letter"]["value"], "G");
+  assert_eq!(response.body["residual_nitrogen_time"]["value"], 49);
+
+  let response = route("post", "/deco", r#"{"depth": 60, "bottom_time": 80}"#);
+  assert_eq!(response.status, STATUS_OK);
+  assert_eq!(response.body["value"]["repetgroup_letter"], "N");
+}
+
+#[test]
+fn api_typed_errors() {
+  let response = route("POST", "/dive", r#"{"depth": 60}"#);
+  assert_eq!(response.status, STATUS_BAD_REQUEST);
+  assert_eq!(response.body["error"]["kind"], "invalid_request");
+
+  let response = route("POST", "/dive", r#"{"depth": 0, "bottom_time": 40}"#);
+  assert_eq!(response.status, STATUS_BAD_REQUEST);
+  assert_eq!(response.body["message"], "invalid request: depth must be greater than 0");
+
+  let response = route("GET", "/dive", "");
+  assert_eq!(response.status, STATUS_METHOD_NOT_ALLOWED);
+  assert_eq!(response.body["error"]["kind"], "method_not_allowed");
+
+  let response = route("GET", "/tables", "");
+  assert_eq!(response.status, STATUS_NOT_FOUND);
+  assert_eq!(response.body["error"]["path"], "/tables");
+}
+
+#[test]
+fn api_outside_the_tables() {
+  let response = route("POST", "/dive", r#"{"depth": 200, "bottom_time": 10}"#);
+  assert_eq!(response.status, STATUS_UNPROCESSABLE_ENTITY);
+  assert_eq!(response.body["error"]["kind"], "outside_no_decompression_table");
+  assert_eq!(response.body["error"]["depth"], 200);
+
+  let response = route("POST", "/dive-plan", r#"{"depth": 100, "bottom_time": 100, "surface_interval_time": 120, "next_dive_depth": 60}"#);
+  assert_eq!(response.status, STATUS_UNPROCESSABLE_ENTITY);
+  assert_eq!(response.body["error"]["kind"], "outside_repetitive_tables");
+  assert_eq!(response.body["error"]["group_letter"], "N/A");
+
+  let response = route("POST", "/dive-plan", r#"{"depth": 100, "bottom_time": 50, "surface_interval_time": 120, "next_dive_depth": 60}"#);
+  assert_eq!(response.status, STATUS_OK);
+  assert!(response.body["residual_nitrogen_time"]["value"].as_u64().unwrap() > 0);
+
+  let response = route("POST", "/deco", r#"{"depth": 60, "bottom_time": 40}"#);
+  assert_eq!(response.status, STATUS_UNPROCESSABLE_ENTITY);
+  assert_eq!(response.body["error"]["kind"], "outside_decompression_table");
+  assert_eq!(response.body["message"], "60 fsw for 40 minutes has no schedule in the air decompression table");
+}
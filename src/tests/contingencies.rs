@@ -0,0 +1,77 @@
+use crate::Dive;
+use crate::contingencies::{blowup, o2_supply_loss, BlowupAction, O2LossProcedure};
+use crate::airtables::DecoStops;
+use crate::errors::DiveError;
+
+#[test]
+fn o2_loss_converts_remaining_time_to_air() {
+  let schedule = Dive::new(100, 50).deco_dive();
+  let procedure = o2_supply_loss(&schedule, 20, 20).unwrap();
+  assert_eq!(procedure, O2LossProcedure::AirDecompression {
+    remaining_o2_time: 4,
+    air_deco_stops: vec![DecoStops { depth: 20, time: 12 }],
+  });
+}
+
+#[test]
+fn o2_loss_never_exceeds_air_schedule() {
+  let schedule = Dive::new(100, 35).deco_dive();
+  let procedure = o2_supply_loss(&schedule, 20, 0).unwrap();
+  assert_eq!(procedure, O2LossProcedure::AirDecompression {
+    remaining_o2_time: 8,
+    air_deco_stops: vec![DecoStops { depth: 20, time: 15 }],
+  });
+}
+
+#[test]
+fn o2_loss_on_surdo2_schedule() {
+  let schedule = Dive::new(100, 100).deco_dive();
+  let procedure = o2_supply_loss(&schedule, 30, 4).unwrap();
+  assert_eq!(procedure, O2LossProcedure::SurDO2 { remaining_o2_time: 76, o2cp: 3.0 });
+}
+
+#[test]
+fn o2_loss_invalid_stop() {
+  let schedule = Dive::new(100, 50).deco_dive();
+  assert_eq!(o2_supply_loss(&schedule, 30, 0), Err(DiveError::StopNotInSchedule { depth: 30 }));
+  assert_eq!(
+    o2_supply_loss(&schedule, 20, 30),
+    Err(DiveError::StopTimeExceeded { depth: 20, time: 30, scheduled: 24 })
+  );
+}
+
+#[test]
+fn blowup_returns_to_omitted_stop() {
+  let schedule = Dive::new(150, 40).deco_dive();
+  assert_eq!(blowup(&schedule, 35, 45), vec![
+    BlowupAction::ReturnToStop { depth: 30, additional_time: 1 },
+    BlowupAction::ResumeDecompression {
+      air_deco_stops: vec![DecoStops { depth: 30, time: 15 }, DecoStops { depth: 20, time: 106 }],
+    },
+  ]);
+}
+
+#[test]
+fn blowup_after_return_interval() {
+  let schedule = Dive::new(100, 50).deco_dive();
+  assert_eq!(blowup(&schedule, 20, 300), vec![BlowupAction::SurDO2 { o2cp: 1.0 }]);
+  assert_eq!(blowup(&schedule, 20, 600), vec![BlowupAction::Treatment { table: String::from("TT5") }]);
+
+  let schedule = Dive::new(100, 70).deco_dive();
+  assert_eq!(blowup(&schedule, 30, 600), vec![BlowupAction::Treatment { table: String::from("TT5") }]);
+
+  let schedule = Dive::new(100, 80).deco_dive();
+  assert_eq!(blowup(&schedule, 30, 600), vec![BlowupAction::Treatment { table: String::from("TT6") }]);
+
+  let schedule = Dive::new(60, 180).deco_dive();
+  assert_eq!(blowup(&schedule, 60, 600), vec![BlowupAction::Treatment { table: String::from("TT6") }]);
+
+  let schedule = Dive::new(150, 40).deco_dive();
+  assert_eq!(blowup(&schedule, 50, 120), vec![BlowupAction::Treatment { table: String::from("TT6") }]);
+}
+
+#[test]
+fn blowup_without_omitted_decompression() {
+  let schedule = Dive::new(100, 50).deco_dive();
+  assert_eq!(blowup(&schedule, 15, 600), vec![BlowupAction::ObserveOnSurface { minutes: 60 }]);
+}
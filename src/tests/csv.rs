@@ -0,0 +1,75 @@
+use crate::{Dive, DivePlan};
+use crate::errors::DiveError;
+use crate::formats::csv::{evaluate_batch, evaluate_csv};
+
+const BATCH: &str = "depth,bottom_time,surface_interval,next_depth
+60,30,1:30,40
+60,80,,
+
+abc,30,,
+55,40,90,
+";
+
+#[test]
+fn csv_batch_rows() {
+  let (columns, results) = evaluate_batch(BATCH);
+  assert_eq!(columns, vec!["depth", "bottom_time", "surface_interval", "next_depth"]);
+  assert_eq!(results.len(), 4);
+
+  let plan = DivePlan::new(60, 30, 90, 40);
+  let first = results[0].evaluation.as_ref().unwrap();
+  assert_eq!(first.no_decompression_limit, 63);
+  assert_eq!(first.group_letter, Dive::new(60, 30).group_letter());
+  assert_eq!(first.repet_letter, Some(plan.repet_letter()));
+  assert_eq!(first.residual_nitrogen_time, Some(plan.residual_nitrogen_time()));
+  assert!(first.deco.is_none());
+
+  let second = results[1].evaluation.as_ref().unwrap();
+  assert_eq!(second.group_letter, String::from("N"));
+  assert_eq!(second.repet_letter, None);
+  assert_eq!(second.deco.as_ref().unwrap().max_time, 80);
+
+  assert_eq!(results[2].line, 5);
+  assert_eq!(results[2].evaluation.as_ref().unwrap_err(), &DiveError::InvalidRecord {
+    line: 5,
+    message: String::from("'abc' is not a valid depth"),
+  });
+  assert_eq!(results[3].evaluation.as_ref().unwrap_err(), &DiveError::InvalidRecord {
+    line: 6,
+    message: String::from("the surface_interval and the next_depth go together"),
+  });
+}
+
+#[test]
+fn csv_batch_output() {
+  let output = evaluate_csv(BATCH);
+  let lines: Vec<&str> = output.lines().collect();
+  let plan = DivePlan::new(60, 30, 90, 40);
+
+  assert_eq!(lines[0], "depth,bottom_time,surface_interval,next_depth,ndl,group_letter,repet_letter,rnt,deco_schedule,advisories,error");
+  assert_eq!(lines[1], format!(
+    "60,30,1:30,40,63,{},{},{},NO-D,,",
+    Dive::new(60, 30).group_letter(),
+    plan.repet_letter(),
+    plan.residual_nitrogen_time(),
+  ));
+  assert_eq!(lines[2], "60,80,,,63,N,,,20/14,no_decompression_limit_exceeded,");
+  assert_eq!(lines[3], "abc,30,,,,,,,,,line 5: 'abc' is not a valid depth");
+  assert_eq!(lines.len(), 5);
+}
+
+#[test]
+fn csv_batch_without_header() {
+  let (_, results) = evaluate_batch("\"60\",30\n40,20,60,50,10\n");
+  assert_eq!(results[0].evaluation.as_ref().unwrap_err(), &DiveError::InvalidRecord {
+    line: 1,
+    message: String::from("expected 4 fields, found 2"),
+  });
+  assert_eq!(results[1].evaluation.as_ref().unwrap_err(), &DiveError::InvalidRecord {
+    line: 2,
+    message: String::from("expected 4 fields, found 5"),
+  });
+
+  let (_, results) = evaluate_batch("40,20,\"2:05\",50\n");
+  assert_eq!(results[0].evaluation.as_ref().unwrap().residual_nitrogen_time, Some(DivePlan::new(40, 20, 125, 50).residual_nitrogen_time()));
+}
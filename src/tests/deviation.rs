@@ -0,0 +1,71 @@
+use crate::Dive;
+use crate::airtables::DecoStops;
+use crate::monitor::deviation::{deviation_report, AscentRateViolation, Recommendation, StopDeviation, StopDeviationKind};
+use crate::profile::{DiveProfile, ProfileSegment};
+
+fn profile(segments: &[(f64, u32)]) -> DiveProfile {
+  let segments = segments.iter()
+    .map(|&(depth, duration)| ProfileSegment { depth, duration })
+    .collect::<Vec<_>>();
+  DiveProfile::from_segments(&segments).unwrap()
+}
+
+#[test]
+fn deviation_dive_as_planned() {
+  let logged = profile(&[(60.0, 60), (60.0, 4740), (20.0, 80), (20.0, 840), (0.0, 40)]);
+  let report = deviation_report(Dive::new(60, 80), &logged).unwrap();
+  assert_eq!((report.actual.depth, report.actual.bottom_time), (60, 80));
+  assert_eq!((report.extra_bottom_time, report.extra_depth), (0, 0));
+  assert!(report.stops.is_empty());
+  assert!(report.ascent_rate_violations.is_empty());
+  assert_eq!(report.recommendation, Recommendation::NoAction);
+}
+
+#[test]
+fn deviation_noisy_stop_as_planned() {
+  let logged = profile(&[(60.0, 60), (60.0, 4740), (20.0, 80), (20.4, 280), (19.9, 280), (20.0, 280), (0.0, 40)]);
+  let report = deviation_report(Dive::new(60, 80), &logged).unwrap();
+  assert_eq!((report.actual.depth, report.actual.bottom_time), (60, 80));
+  assert_eq!(report.recommendation, Recommendation::NoAction);
+}
+
+#[test]
+fn deviation_shortened_stop() {
+  let logged = profile(&[(60.0, 60), (60.0, 4740), (20.0, 80), (20.0, 420), (0.0, 40)]);
+  let report = deviation_report(Dive::new(60, 80), &logged).unwrap();
+  assert_eq!(report.stops, vec![
+    StopDeviation { depth: 20, planned: 14, actual: 420, kind: StopDeviationKind::Shortened },
+  ]);
+  assert_eq!(report.recommendation, Recommendation::OmittedDecompression {
+    omitted_stops: vec![DecoStops { depth: 20, time: 7 }],
+  });
+}
+
+#[test]
+fn deviation_extra_bottom_time() {
+  let logged = profile(&[(60.0, 60), (60.0, 2040), (0.0, 120)]);
+  let report = deviation_report(Dive::new(60, 30), &logged).unwrap();
+  assert_eq!(report.extra_bottom_time, 5);
+  assert_eq!(report.recommendation, Recommendation::Penalty {
+    repet_group: Dive::new(60, 35).group_letter(),
+  });
+
+  let logged = profile(&[(60.0, 60), (60.0, 4140), (0.0, 120)]);
+  let report = deviation_report(Dive::new(60, 60), &logged).unwrap();
+  assert_eq!(report.extra_bottom_time, 10);
+  assert!(report.stops.is_empty());
+  match report.recommendation {
+    Recommendation::OmittedDecompression { omitted_stops } => assert!(!omitted_stops.is_empty()),
+    recommendation => panic!("unexpected recommendation {:?}", recommendation),
+  }
+}
+
+#[test]
+fn deviation_fast_ascent() {
+  let logged = profile(&[(60.0, 60), (60.0, 1740), (0.0, 30)]);
+  let report = deviation_report(Dive::new(60, 30), &logged).unwrap();
+  assert_eq!(report.ascent_rate_violations, vec![AscentRateViolation { time: 1830, rate: 120.0 }]);
+  assert_eq!(report.recommendation, Recommendation::Penalty {
+    repet_group: Dive::new(60, 30).group_letter(),
+  });
+}
@@ -0,0 +1,96 @@
+use crate::{Dive, DivePlan};
+use crate::errors::DiveError;
+use crate::formats::{dl7, subsurface};
+use crate::timekeeping::DateTime;
+
+const LOGBOOK: &str = r#"<divelog program='subsurface' version='3'>
+<dives>
+<dive number='1' date='2024-05-01' time='09:00:00' duration='33:00 min'>
+  <divecomputer model='Suunto Zoop'>
+  <sample time='1:00 min' depth='18.2 m' />
+  <sample time='30:00 min' depth='18.2 m' />
+  <sample time='33:00 min' depth='0.0 m' />
+  </divecomputer>
+</dive>
+<dive number='2' date='2024-05-01' time='23:03:00' duration='21:00 min'>
+  <divecomputer model='Suunto Zoop'>
+  <sample time='1:00 min' depth='12.1 m' />
+  <sample time='20:00 min' depth='12.1 m' />
+  <sample time='21:00 min' depth='0.0 m' />
+  </divecomputer>
+</dive>
+</dives>
+</divelog>"#;
+
+const DEEP_AIR_LOGBOOK: &str = r#"<divelog program='subsurface' version='3'>
+<dives>
+<dive number='1' date='2024-05-02' time='09:00:00' duration='40:00 min'>
+  <divecomputer model='Suunto Zoop'>
+  <sample time='1:00 min' depth='58.0 m' />
+  <sample time='10:00 min' depth='58.0 m' />
+  <sample time='40:00 min' depth='0.0 m' />
+  </divecomputer>
+</dive>
+</dives>
+</divelog>"#;
+
+fn exported() -> DateTime {
+  "2024-05-03T08:00:00".parse().unwrap()
+}
+
+#[test]
+fn dl7_air_day() {
+  let dives = subsurface::import(LOGBOOK).unwrap();
+  let dl7 = dl7::export(&dives, 0.21, exported()).unwrap();
+  let lines: Vec<&str> = dl7.lines().collect();
+  let plan = DivePlan::new(60, 30, 810, 40);
+
+  assert_eq!(lines[0], format!("FSH|^~<>{{}}|diving-decompression^{}|ZXU|20240503080000|", env!("CARGO_PKG_VERSION")));
+  assert_eq!(lines[1], "ZRH|^~<>{}|||FSWG|ThFt|F|PSIA|CF|");
+  assert_eq!(lines[2], "ZAR{");
+  assert_eq!(lines[3], format!(
+    "USN-AIR-REV7|1|60|30|NO-D|{}|{}|{}|",
+    Dive::new(60, 30).group_letter(),
+    plan.repet_letter(),
+    plan.residual_nitrogen_time(),
+  ));
+  let equivalent = plan.repetitive_dive(20);
+  assert_eq!(lines[4], format!("USN-AIR-REV7|2|40|{}|NO-D|{}|||", equivalent.bottom_time, equivalent.group_letter()));
+  assert_eq!(lines[5], "ZAR}");
+
+  assert_eq!(lines[6], "ZDH|1|1|I|Q1S|20240501090000||||");
+  assert_eq!(lines[7], "ZDP{");
+  assert_eq!(lines[8], "|0.00|0.0|AIR|0.21|");
+  assert_eq!(lines[9], "|1.00|59.7||0.59|");
+  assert_eq!(lines[12], "ZDP}");
+  assert_eq!(lines[13], "ZDT|1|1|59.7|20240501093300||");
+  assert_eq!(lines.last().copied(), Some("ZDT|2|2|39.7|20240501232400||"));
+}
+
+#[test]
+fn dl7_nitrox_tables() {
+  let dives = subsurface::import(LOGBOOK).unwrap();
+  let dl7 = dl7::export(&dives[..1], 0.32, exported()).unwrap();
+  assert!(dl7.contains("|0.00|0.0|EAN32|0.32|"));
+  assert!(dl7.lines().any(|line| line.starts_with("USN-AIR-REV7|1|48|30|NO-D|")));
+
+  assert_eq!(dl7::export(&dives, 0.50, exported()).unwrap_err(), DiveError::InvalidOxygenFraction { fo2: 0.50 });
+
+  let mut undated = dives;
+  undated[1].start = None;
+  assert_eq!(dl7::export(&undated, 0.21, exported()).unwrap_err(), DiveError::MissingStartTime { index: 1 });
+}
+
+#[test]
+fn dl7_deep_air_schedule() {
+  let dives = subsurface::import(DEEP_AIR_LOGBOOK).unwrap();
+  let dive = dives[0].dive();
+  let dl7 = dl7::export(&dives, 0.21, exported()).unwrap();
+
+  assert!(dive.depth > 187 && dive.depth < 200);
+  assert_eq!(dl7.lines().nth(3), Some(format!(
+    "USN-AIR-REV7|1|{}|10|200/10|{}|||",
+    dive.depth,
+    dive.deco_dive().repetgroup_letter,
+  ).as_str()));
+}
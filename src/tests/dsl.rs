@@ -0,0 +1,77 @@
+use crate::{Dive, DivePlan, ExposureModifier};
+use crate::dsl::{evaluate, parse, parse_day, StatementKind};
+use crate::errors::DiveError;
+use crate::formats::{annotate, dive_plans};
+
+fn syntax(line: usize, column: usize, message: &str) -> DiveError {
+  DiveError::Syntax { line, column, message: String::from(message) }
+}
+
+#[test]
+fn dsl_parse_statements() {
+  let statements = parse("dive 60fsw 40min; surface 1h30; dive 50fsw 30min\n# afternoon\nsurface 2h\ndive 12m 20min cold").unwrap();
+  assert_eq!(statements.len(), 5);
+  match statements[1].kind {
+    StatementKind::Surface { minutes } => assert_eq!(minutes, 90),
+    kind => panic!("unexpected statement {:?}", kind),
+  }
+  assert_eq!((statements[2].line, statements[2].column), (1, 33));
+  match statements[4].kind {
+    StatementKind::Dive { dive } => {
+      assert_eq!((dive.depth, dive.bottom_time), (40, 20));
+      assert_eq!(dive.exposure, ExposureModifier::Cold);
+    }
+    kind => panic!("unexpected statement {:?}", kind),
+  }
+
+  let day = parse_day("dive 60ft 40min; surface 45min; dive 50fsw 1h05min strenuous cold; surface 3h").unwrap();
+  assert_eq!(day.surface_intervals, vec![45]);
+  assert_eq!(day.dives[1].bottom_time, 65);
+  assert_eq!(day.dives[1].exposure, ExposureModifier::ColdAndStrenuous);
+}
+
+#[test]
+fn dsl_syntax_errors() {
+  assert_eq!(parse("dive 60fsw 40min\n  swim 60fsw").unwrap_err(), syntax(2, 3, "expected 'dive' or 'surface', found 'swim'"));
+  assert_eq!(parse("dive 60 40min").unwrap_err(), syntax(1, 6, "'60' is not a depth such as 60fsw or 18m"));
+  assert_eq!(parse("dive 60fsw; surface 1h").unwrap_err(), syntax(1, 11, "expected a bottom time such as 40min"));
+  assert_eq!(parse("dive 60fsw 40min;surface 1h75").unwrap_err(), syntax(1, 26, "'1h75' is not a surface interval such as 1h30"));
+  assert_eq!(parse("dive 60fsw 40min warm").unwrap_err(), syntax(1, 18, "unexpected 'warm'"));
+  assert_eq!(parse_day("surface 1h").unwrap_err(), syntax(1, 1, "the plan must start with a dive"));
+  assert_eq!(parse_day("dive 60fsw 40min\ndive 50fsw 30min").unwrap_err(), syntax(2, 1, "a dive must follow a surface interval"));
+  assert_eq!(parse_day("# nothing planned").unwrap_err(), syntax(1, 1, "the plan has no dive"));
+}
+
+#[test]
+fn dsl_evaluate_day() {
+  let evaluations = evaluate("dive 60fsw 40min; surface 1h30; dive 50fsw 30min").unwrap();
+  let plan = DivePlan::new(60, 40, 90, 50);
+
+  assert_eq!(evaluations.len(), 2);
+  assert!(evaluations[0].repet_letter.is_none());
+  assert_eq!(evaluations[0].group_letter.value, Dive::new(60, 40).group_letter());
+  assert!(evaluations[0].deco.is_none());
+
+  let repetitive = &evaluations[1];
+  assert_eq!(repetitive.repet_letter.as_ref().unwrap().value, plan.repet_letter());
+  assert_eq!(repetitive.residual_nitrogen_time.as_ref().unwrap().value, plan.residual_nitrogen_time());
+  let equivalent = plan.repetitive_dive(30);
+  assert_eq!((repetitive.equivalent_dive.depth, repetitive.equivalent_dive.bottom_time), (50, equivalent.bottom_time));
+  assert_eq!(repetitive.deco.is_some(), equivalent.bottom_time > Dive::new(50, 0).no_decompression_limit());
+}
+
+#[test]
+fn dsl_day_follows_the_logged_dive_chain() {
+  let day = parse_day("dive 60fsw 40min; surface 1h30; dive 50fsw 30min cold; surface 2h; dive 40fsw 20min").unwrap();
+  let dives = day.logged_dives();
+  assert_eq!(dives.iter().map(|dive| dive.surface_interval).collect::<Vec<_>>(), vec![None, Some(5400), Some(7200)]);
+  assert_eq!(day.plans().len(), 2);
+  assert_eq!(day.plans()[1].residual_nitrogen_time(), dive_plans(&dives)[1].unwrap().residual_nitrogen_time());
+
+  let evaluations = day.evaluate();
+  for (evaluation, annotation) in evaluations.iter().zip(annotate(&dives).iter()) {
+    assert_eq!(evaluation.equivalent_dive.bottom_time, annotation.equivalent_dive.bottom_time);
+    assert_eq!(evaluation.deco.is_none(), annotation.within_no_decompression);
+  }
+  assert_eq!(evaluations[1].equivalent_dive.exposure, ExposureModifier::Cold);
+}
@@ -0,0 +1,45 @@
+use crate::{Dive, DivePlan, ExposureModifier};
+use crate::advisories::AdvisoryCode;
+
+#[test]
+fn cold_dive_uses_next_group() {
+  let d = Dive::new(35, 42);
+  assert_eq!(d.group_letter(), String::from("D"));
+  let cold = d.with_exposure(ExposureModifier::Cold);
+  assert_eq!(cold.group_letter(), String::from("E"));
+  assert!(cold.advised_group_letter().has(AdvisoryCode::ScheduleAdjusted));
+}
+
+#[test]
+fn strenuous_dive_at_limit_requires_decompression() {
+  let d = Dive::new(60, 63).with_exposure(ExposureModifier::Strenuous);
+  assert_eq!(d.group_letter(), String::from("this dive is out of the time range for no-decompression air dives"));
+  assert!(d.advised_no_decompression_limit().has(AdvisoryCode::NoDecompressionLimitExceeded));
+  let deco = d.deco_dive();
+  assert_eq!((deco.min_time, deco.max_time), (64, 65));
+}
+
+#[test]
+fn cold_and_strenuous_deco_dive_uses_next_schedule() {
+  let d = Dive::new(100, 33);
+  let deco = d.deco_dive();
+  assert_eq!((deco.min_time, deco.max_time), (31, 35));
+  let deco = d.with_exposure(ExposureModifier::ColdAndStrenuous).advised_deco_dive();
+  assert_eq!((deco.value.min_time, deco.value.max_time), (36, 40));
+  assert!(deco.has(AdvisoryCode::ScheduleAdjusted));
+}
+
+#[test]
+fn adjusted_repetitive_dive() {
+  let plan = DivePlan::new(60, 40, 120, 50);
+  let cold = plan.with_exposure(ExposureModifier::Cold);
+  assert_ne!(plan.repet_letter(), cold.repet_letter());
+  assert!(cold.residual_nitrogen_time() >= plan.residual_nitrogen_time());
+  assert!(cold.advised_repet_letter().has(AdvisoryCode::ScheduleAdjusted));
+  assert_eq!(DivePlan::from_dive(Dive::new(60, 40).with_exposure(ExposureModifier::Cold), 120, 50).repet_letter(), cold.repet_letter());
+
+  let repetitive = cold.repetitive_dive(30);
+  assert_eq!(repetitive.exposure, ExposureModifier::Cold);
+  assert!(repetitive.advised_group_letter().has(AdvisoryCode::ScheduleAdjusted));
+  assert_eq!(plan.repetitive_dive(30).exposure, ExposureModifier::None);
+}
@@ -0,0 +1,37 @@
+use crate::Dive;
+use crate::advisories::AdvisoryCode;
+use crate::gas::{dive_segments, scuba_gas_plan, Cylinder, GasPhase, ReserveRule};
+
+#[test]
+fn gas_no_decompression_dive() {
+  let segments = dive_segments(Dive::new(66, 30), 1.0);
+  let phases: Vec<GasPhase> = segments.iter().map(|segment| segment.phase).collect();
+  assert_eq!(phases, vec![GasPhase::Descent, GasPhase::Bottom, GasPhase::Travel]);
+  assert!((segments[0].volume - 1.1 * 2.0).abs() < 1e-9);
+  assert!((segments[1].volume - 3.0 * 28.9).abs() < 1e-9);
+  assert!((segments[2].volume - 2.0 * 2.2).abs() < 1e-9);
+}
+
+#[test]
+fn gas_decompression_dive_stops() {
+  let segments = dive_segments(Dive::new(100, 50), 1.0);
+  let stops: Vec<f64> = segments.iter()
+    .filter(|segment| segment.phase == GasPhase::Stop)
+    .map(|segment| segment.minutes)
+    .collect();
+  assert_eq!(stops, vec![47.0]);
+}
+
+#[test]
+fn gas_reserve() {
+  let cylinder = Cylinder::new(77.4, 3000.0, 3000.0);
+  let plan = scuba_gas_plan(Dive::new(60, 30), 0.7, cylinder, ReserveRule::Pressure(500.0));
+  assert!(plan.reserve_respected);
+  assert!(plan.remaining_pressure > 500.0);
+  assert!(plan.advisories.is_empty());
+
+  let plan = scuba_gas_plan(Dive::new(100, 50), 0.7, cylinder, ReserveRule::Thirds);
+  assert_eq!(plan.reserve_pressure, 1000.0);
+  assert!(!plan.reserve_respected);
+  assert!(plan.advisories.iter().any(|advisory| advisory.code == AdvisoryCode::GasReserveBroken));
+}
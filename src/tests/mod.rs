@@ -1,5 +1,47 @@
 #[cfg(test)]
 use super::Dive;
+#[cfg(test)]
+mod advisories;
+#[cfg(test)]
+mod api;
+#[cfg(test)]
+mod contingencies;
+#[cfg(test)]
+mod csv;
+#[cfg(test)]
+mod deviation;
+#[cfg(test)]
+mod dl7;
+#[cfg(test)]
+mod dsl;
+#[cfg(test)]
+mod exposure;
+#[cfg(test)]
+mod gas;
+#[cfg(test)]
+mod monitor;
+#[cfg(test)]
+mod nitrox;
+#[cfg(test)]
+mod oxygen;
+#[cfg(test)]
+mod oxygen_supply;
+#[cfg(test)]
+mod profile;
+#[cfg(test)]
+mod station;
+#[cfg(test)]
+mod subsurface;
+#[cfg(test)]
+mod surface_supplied;
+#[cfg(test)]
+mod tender;
+#[cfg(test)]
+mod timekeeping;
+#[cfg(test)]
+mod treatment;
+#[cfg(test)]
+mod uddf;
 
 #[test]
 fn calculate_1() {
@@ -89,4 +131,14 @@ fn calculate_10() {
   let gl = d.group_letter();
   assert_eq!(ndl, 74);
   assert_eq!(gl, String::from("L"));
-}
\ No newline at end of file
+}
+
+#[test]
+fn calculate_deco_between_rows() {
+  // 75 minutes at 60 fsw falls inside the 71-80 minute schedule
+  let deco = Dive::new(60, 75).deco_dive();
+  assert_eq!(deco.max_time, 80);
+  assert_eq!(deco.repetgroup_letter, String::from("N"));
+  assert_eq!(deco.air_deco_stops.len(), 1);
+  assert_eq!((deco.air_deco_stops[0].depth, deco.air_deco_stops[0].time), (20, 14));
+}
@@ -0,0 +1,131 @@
+use crate::errors::DiveError;
+use crate::monitor::{DiveMonitor, DivePhase, MonitorEvent};
+
+fn feed(monitor: &mut DiveMonitor, samples: &[(u32, f64)]) -> Vec<MonitorEvent> {
+  samples.iter()
+    .flat_map(|&(time, depth)| monitor.update(time, depth).unwrap().events)
+    .collect()
+}
+
+fn bottom(depth: f64, from: u32, to: u32) -> Vec<(u32, f64)> {
+  (from..=to).step_by(60).map(|time| (time, depth)).collect()
+}
+
+#[test]
+fn monitor_no_decompression_dive() {
+  let mut monitor = DiveMonitor::new();
+  let mut samples = vec![(0, 0.0), (30, 30.0)];
+  samples.extend(bottom(60.0, 60, 1800));
+  let mut events = feed(&mut monitor, &samples);
+  assert_eq!(monitor.state().phase, DivePhase::Bottom);
+  assert_eq!(monitor.state().bottom_time, 30);
+  assert_eq!(monitor.state().remaining_no_decompression, Some(33));
+
+  events.extend(feed(&mut monitor, &[(1860, 30.0), (1920, 0.0)]));
+  assert_eq!(events, vec![
+    MonitorEvent::LeftSurface { time: 0 },
+    MonitorEvent::LeftBottom { time: 1800 },
+    MonitorEvent::ReachedSurface { time: 1920 },
+  ]);
+  assert_eq!(monitor.state().phase, DivePhase::Surface);
+  assert_eq!(monitor.state().max_depth, 60);
+  assert!(monitor.state().schedule.is_none());
+}
+
+#[test]
+fn monitor_decompression_dive() {
+  let mut monitor = DiveMonitor::new();
+  let mut samples = vec![(0, 0.0), (30, 30.0)];
+  samples.extend(bottom(60.0, 60, 4800));
+  samples.extend(vec![(4840, 40.0), (4880, 20.0)]);
+  samples.extend(bottom(20.5, 4940, 5720));
+  samples.push((5760, 0.0));
+
+  let mut events = vec![];
+  for &(time, depth) in samples.iter() {
+    let update = monitor.update(time, depth).unwrap();
+    if time == 5000 {
+      assert_eq!(update.state.phase, DivePhase::Stop);
+      assert_eq!(update.state.next_stop.unwrap().elapsed, 120);
+      assert_eq!(update.state.next_stop.unwrap().required, 840);
+    }
+    events.extend(update.events);
+  }
+
+  assert_eq!(events, vec![
+    MonitorEvent::LeftSurface { time: 0 },
+    MonitorEvent::NoDecompressionLimitExceeded { time: 3840 },
+    MonitorEvent::LeftBottom { time: 4800 },
+    MonitorEvent::StopReached { time: 4880, depth: 20 },
+    MonitorEvent::StopCompleted { time: 5720, depth: 20 },
+    MonitorEvent::ReachedSurface { time: 5760 },
+  ]);
+  let schedule = monitor.state().schedule.clone().unwrap();
+  assert_eq!((schedule.min_time, schedule.max_time), (71, 80));
+  assert_eq!(monitor.state().bottom_time, 80);
+  assert_eq!(monitor.state().remaining_no_decompression, None);
+}
+
+#[test]
+fn monitor_fast_ascent_and_missed_stop() {
+  let mut monitor = DiveMonitor::new();
+  let mut samples = vec![(0, 0.0)];
+  samples.extend(bottom(60.0, 60, 4800));
+  samples.extend(vec![(4830, 30.0), (4860, 15.0), (4920, 0.0)]);
+  let events = feed(&mut monitor, &samples);
+
+  assert_eq!(events[2..], [
+    MonitorEvent::AscentTooFast { time: 4830, rate: 60.0 },
+    MonitorEvent::LeftBottom { time: 4800 },
+    MonitorEvent::StopMissed { time: 4860, depth: 20, remaining: 840 },
+    MonitorEvent::ReachedSurface { time: 4920 },
+  ]);
+}
+
+#[test]
+fn monitor_multi_level_dive() {
+  let mut monitor = DiveMonitor::new();
+  let mut samples = vec![(0, 0.0)];
+  samples.extend(bottom(100.0, 60, 1200));
+  samples.extend(vec![(1230, 80.0), (1260, 60.0)]);
+  samples.extend(bottom(60.0, 1320, 2400));
+  let events = feed(&mut monitor, &samples);
+
+  assert_eq!(events, vec![
+    MonitorEvent::LeftSurface { time: 0 },
+    MonitorEvent::NoDecompressionLimitExceeded { time: 1560 },
+  ]);
+  assert_eq!(monitor.state().phase, DivePhase::Bottom);
+  assert_eq!(monitor.state().bottom_time, 40);
+  assert_eq!(monitor.state().schedule.clone().unwrap().max_time, 40);
+  assert!(monitor.state().next_stop.is_none());
+
+  let events = feed(&mut monitor, &[(2440, 40.0), (2480, 20.0)]);
+  assert_eq!(events, vec![MonitorEvent::LeftBottom { time: 1200 }]);
+  assert_eq!(monitor.state().bottom_time, 20);
+  assert_eq!(monitor.state().remaining_no_decompression, Some(5));
+  assert!(monitor.state().schedule.is_none());
+}
+
+#[test]
+fn monitor_outside_decompression_table() {
+  let mut monitor = DiveMonitor::new();
+  let mut samples = vec![(0, 0.0)];
+  samples.extend(bottom(320.0, 60, 300));
+  let events = feed(&mut monitor, &samples);
+
+  assert_eq!(events, vec![
+    MonitorEvent::LeftSurface { time: 0 },
+    MonitorEvent::NoDecompressionLimitExceeded { time: 60 },
+    MonitorEvent::OutsideDecompressionTable { time: 60 },
+  ]);
+  assert_eq!(monitor.state().schedule.clone().unwrap().max_time, 0);
+}
+
+#[test]
+fn monitor_rejects_unordered_samples() {
+  let mut monitor = DiveMonitor::new();
+  monitor.update(0, 0.0).unwrap();
+  monitor.update(60, 20.0).unwrap();
+  assert_eq!(monitor.update(30, 25.0).unwrap_err(), DiveError::UnorderedSamples { index: 2 });
+}
@@ -0,0 +1,44 @@
+use crate::nitrox::NitroxDive;
+use crate::errors::DiveError;
+
+#[test]
+fn nitrox_equivalent_air_depth() {
+  assert_eq!(NitroxDive::new(90, 30, 0.32).equivalent_air_depth(), 73);
+  assert_eq!(NitroxDive::new(100, 30, 0.36).equivalent_air_depth(), 75);
+  assert_eq!(NitroxDive::new(99, 30, 0.21).equivalent_air_depth(), 99);
+  assert_eq!(NitroxDive::new(10, 30, 0.36).equivalent_air_depth(), 2);
+}
+
+#[test]
+fn nitrox_maximum_operating_depth() {
+  assert_eq!(NitroxDive::new(90, 30, 0.32).maximum_operating_depth(), 111);
+  assert_eq!(NitroxDive::new(90, 30, 0.36).maximum_operating_depth(), 95);
+  assert_eq!(
+    NitroxDive::new(100, 30, 0.36).plan().unwrap_err(),
+    DiveError::MaximumOperatingDepthExceeded { depth: 100, maximum_operating_depth: 95 }
+  );
+  assert_eq!(
+    NitroxDive::new(60, 30, 0.5).plan().unwrap_err(),
+    DiveError::InvalidOxygenFraction { fo2: 0.5 }
+  );
+}
+
+#[test]
+fn nitrox_no_decompression_plan() {
+  let plan = NitroxDive::new(90, 30, 0.32).plan().unwrap();
+  assert_eq!(plan.equivalent_air_depth, 73);
+  assert_eq!(plan.no_decompression_limit, 39);
+  assert_eq!(plan.group_letter, String::from("H"));
+  assert!(plan.deco.is_none());
+  assert!((plan.po2 - 1.193).abs() < 0.001);
+  assert_eq!(plan.o2_exposure_time, 30);
+  assert!(plan.advisories.is_empty());
+}
+
+#[test]
+fn nitrox_decompression_plan() {
+  let plan = NitroxDive::new(90, 60, 0.32).plan().unwrap();
+  let deco = plan.deco.unwrap();
+  assert!(deco.min_time <= 60 && 60 <= deco.max_time);
+  assert!(!deco.air_deco_stops.is_empty());
+}
@@ -0,0 +1,71 @@
+use crate::Dive;
+use crate::advisories::{AdvisoryCode, Severity};
+use crate::nitrox::NitroxDive;
+use crate::oxygen::{chamber_segments, OxygenExposure, OxygenSegment};
+
+#[test]
+fn oxygen_segment_figures() {
+  let segment = OxygenSegment::new(1.4, 30);
+  assert!((segment.cns() - 20.0).abs() < 1e-9);
+  assert!((segment.otu() - 30.0 * 1.8_f64.powf(0.83)).abs() < 1e-9);
+  assert_eq!(OxygenSegment::new(0.4, 60).cns(), 0.0);
+  assert_eq!(OxygenSegment::new(0.4, 60).otu(), 0.0);
+  assert!((OxygenSegment::new(1.35, 90).cns() - 60.0).abs() < 1e-9);
+  assert_eq!(OxygenSegment::new(1.9, 30).cns(), 0.0);
+}
+
+#[test]
+fn oxygen_chamber_periods() {
+  let segments = chamber_segments(2.5);
+  assert_eq!(segments.len(), 5);
+  assert!((segments[0].po2 - 83.0 / 33.0).abs() < 1e-9);
+  assert!((segments[4].po2 - 73.0 / 33.0).abs() < 1e-9);
+  assert_eq!(chamber_segments(0.5).len(), 1);
+}
+
+#[test]
+fn oxygen_exposure_across_the_day() {
+  let mut exposure = OxygenExposure::new();
+  exposure.add_nitrox(&NitroxDive::new(90, 30, 0.32).plan().unwrap());
+  exposure.add_o2_stops(&Dive::new(100, 50).deco_dive());
+  assert_eq!(exposure.segments.len(), 2);
+  let cns = exposure.cns;
+  exposure.add_surface_interval(90);
+  assert!((exposure.cns - cns / 2.0).abs() < 1e-9);
+  assert!(exposure.advisories().is_empty());
+}
+
+#[test]
+fn oxygen_exposure_limits() {
+  let mut exposure = OxygenExposure::new();
+  exposure.add_segment(OxygenSegment::new(1.4, 120));
+  exposure.add_segment(OxygenSegment::new(1.5, 40));
+  let advisories = exposure.advisories();
+  assert!(advisories.iter().any(|advisory| advisory.code == AdvisoryCode::CnsLimitExceeded));
+}
+
+#[test]
+fn oxygen_chamber_periods_out_of_cns_range() {
+  let mut exposure = OxygenExposure::new();
+  exposure.add_chamber_periods(2.0);
+  assert_eq!(exposure.cns, 0.0);
+  assert_eq!(exposure.out_of_range_minutes, 60);
+  assert!(exposure.otu > 0.0);
+  let advisories = exposure.advisories();
+  assert!(advisories.iter().any(|advisory| advisory.code == AdvisoryCode::OxygenOutsideCnsLimits));
+  assert_eq!(advisories[0].severity, Severity::Warning);
+}
+
+#[test]
+fn oxygen_stop_at_20_fsw() {
+  let segment = OxygenSegment::at_depth(20, 1.0, 30);
+  assert!(segment.po2 > 1.6);
+  assert!(segment.within_cns_limits());
+  assert!((segment.cns() - 30.0 / 45.0 * 100.0).abs() < 1e-9);
+  assert!(!OxygenSegment::at_depth(30, 1.0, 30).within_cns_limits());
+
+  let mut exposure = OxygenExposure::new();
+  exposure.add_o2_stops(&Dive::new(100, 50).deco_dive());
+  assert_eq!(exposure.out_of_range_minutes, 0);
+  assert!(exposure.cns > 0.0);
+}
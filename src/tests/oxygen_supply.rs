@@ -0,0 +1,38 @@
+use crate::Dive;
+use crate::gas::oxygen_supply::{OxygenQuantity, OxygenUse};
+
+#[test]
+fn oxygen_supply_in_water_stops() {
+  let mut quantity = OxygenQuantity::new(1.5, 1.0);
+  quantity.add_o2_stops(&Dive::new(100, 50).deco_dive());
+  assert_eq!(quantity.demand.len(), 1);
+  assert_eq!(quantity.demand[0].usage, OxygenUse::InWater);
+  assert!((quantity.total_volume() - 1.5 * 53.0 / 33.0 * 24.0).abs() < 1e-9);
+}
+
+#[test]
+fn oxygen_supply_chamber_with_tender() {
+  let mut quantity = OxygenQuantity::new(1.5, 1.0);
+  quantity.add_chamber_periods(2.0);
+  let tender: Vec<u16> = quantity.demand.iter()
+    .filter(|period| period.usage == OxygenUse::ChamberTender)
+    .map(|period| period.minutes)
+    .collect();
+  assert_eq!(tender, vec![30]);
+  let diver_minutes: u16 = quantity.demand.iter()
+    .filter(|period| period.usage == OxygenUse::ChamberDiver)
+    .map(|period| period.minutes)
+    .sum();
+  assert_eq!(diver_minutes, 60);
+}
+
+#[test]
+fn oxygen_supply_cylinders_for_the_day() {
+  let mut quantity = OxygenQuantity::new(1.5, 1.0);
+  quantity.add_o2_stops(&Dive::new(100, 50).deco_dive());
+  quantity.add_chamber_periods(Dive::new(100, 100).deco_dive().o2cp);
+  quantity.add_period(OxygenUse::ChamberDiver, 60, 20);
+  let load = quantity.load(50.0);
+  assert_eq!(load.cylinders, (quantity.total_volume() / 50.0).ceil() as u16);
+  assert!(f64::from(load.cylinders) * 50.0 >= load.total_volume);
+}
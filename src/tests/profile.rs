@@ -0,0 +1,98 @@
+use crate::errors::DiveError;
+use crate::profile::{DiveProfile, ProfileSample, ProfileSegment};
+
+fn segment(depth: f64, duration: u32) -> ProfileSegment {
+  ProfileSegment { depth, duration }
+}
+
+#[test]
+fn profile_square_dive() {
+  let profile = DiveProfile::from_segments(&[
+    segment(60.0, 60),
+    segment(60.0, 39 * 60),
+    segment(0.0, 120),
+  ]).unwrap();
+  let reduction = profile.reduce().unwrap();
+  assert_eq!(reduction.max_depth, 60);
+  assert_eq!((reduction.leave_surface, reduction.leave_bottom), (0, 2400));
+  assert_eq!(reduction.reach_surface, Some(2520));
+  assert_eq!(reduction.bottom_time, 40);
+}
+
+#[test]
+fn profile_multi_level_dive() {
+  let profile = DiveProfile::from_samples(vec![
+    ProfileSample { time: 0, depth: 0.0 },
+    ProfileSample { time: 120, depth: 0.5 },
+    ProfileSample { time: 180, depth: 45.0 },
+    ProfileSample { time: 900, depth: 58.2 },
+    ProfileSample { time: 1500, depth: 40.0 },
+    ProfileSample { time: 2100, depth: 52.0 },
+    ProfileSample { time: 2130, depth: 52.0 },
+    ProfileSample { time: 2400, depth: 20.0 },
+    ProfileSample { time: 2700, depth: 15.0 },
+    ProfileSample { time: 2760, depth: 0.0 },
+  ]).unwrap();
+  let evaluation = profile.evaluate().unwrap();
+  assert_eq!(evaluation.reduction.max_depth, 59);
+  assert_eq!(evaluation.reduction.leave_surface, 120);
+  assert_eq!(evaluation.reduction.leave_bottom, 2130);
+  assert_eq!(evaluation.reduction.bottom_time, 34);
+  assert_eq!(evaluation.no_decompression_limit, 63);
+  assert_eq!(evaluation.group_letter.value, String::from("G"));
+  assert!(evaluation.deco.is_none());
+}
+
+#[test]
+fn profile_decompression_dive() {
+  let profile = DiveProfile::from_segments(&[
+    segment(100.0, 100),
+    segment(100.0, 50 * 60 - 100),
+    segment(20.0, 160),
+    segment(20.0, 47 * 60),
+    segment(0.0, 40),
+  ]).unwrap();
+  let evaluation = profile.evaluate().unwrap();
+  assert_eq!(evaluation.reduction.bottom_time, 50);
+  let deco = evaluation.deco.unwrap();
+  assert_eq!((deco.value.min_time, deco.value.max_time), (46, 50));
+}
+
+#[test]
+fn profile_below_the_no_decompression_table() {
+  let profile = DiveProfile::from_segments(&[segment(195.0, 200), segment(195.0, 400), segment(0.0, 400)]).unwrap();
+  let evaluation = profile.evaluate().unwrap();
+  assert_eq!((evaluation.reduction.max_depth, evaluation.reduction.bottom_time), (195, 10));
+  assert_eq!(evaluation.deco.unwrap().value.max_time, 10);
+}
+
+#[test]
+fn profile_noisy_decompression_stop() {
+  let mut samples = vec![
+    ProfileSample { time: 0, depth: 0.0 },
+    ProfileSample { time: 100, depth: 100.0 },
+    ProfileSample { time: 3000, depth: 100.0 },
+    ProfileSample { time: 3160, depth: 20.0 },
+  ];
+  let wobble = [20.4, 19.9, 20.0];
+  samples.extend((1..=47).map(|minute| ProfileSample { time: 3160 + minute * 60, depth: wobble[minute as usize % 3] }));
+  samples.push(ProfileSample { time: 6020, depth: 0.0 });
+
+  let reduction = DiveProfile::from_samples(samples).unwrap().reduce().unwrap();
+  assert_eq!(reduction.leave_bottom, 3000);
+  assert_eq!(reduction.bottom_time, 50);
+}
+
+#[test]
+fn profile_invalid_samples() {
+  assert_eq!(DiveProfile::from_samples(vec![]).unwrap_err(), DiveError::EmptyProfile);
+  assert_eq!(
+    DiveProfile::from_samples(vec![
+      ProfileSample { time: 60, depth: 10.0 },
+      ProfileSample { time: 30, depth: 20.0 },
+    ]).unwrap_err(),
+    DiveError::UnorderedSamples { index: 1 }
+  );
+  let surface = DiveProfile::from_segments(&[segment(0.5, 60)]).unwrap();
+  assert_eq!(surface.reduce().unwrap_err(), DiveError::EmptyProfile);
+}
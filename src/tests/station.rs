@@ -0,0 +1,102 @@
+use crate::{Dive, DivePlan};
+use crate::errors::DiveError;
+use crate::station::{Diver, DiverStatus, StationEvent, StopCountdown};
+
+#[test]
+fn station_decompression_dive() {
+  let mut diver = Diver::new("Smith", 60);
+  diver.handle(StationEvent::LeftSurface, 0).unwrap();
+  diver.handle(StationEvent::ReachedBottom, 60).unwrap();
+
+  let view = diver.view(1800);
+  assert_eq!(view.status, DiverStatus::Bottom);
+  assert_eq!(view.timer, 1800);
+  assert_eq!(view.remaining_no_decompression, Some(33));
+  assert_eq!(diver.view(4200).remaining_no_decompression, Some(-7));
+
+  diver.handle(StationEvent::LeftBottom, 4800).unwrap();
+  assert_eq!(diver.schedule().map(|schedule| schedule.max_time), Some(80));
+  let view = diver.view(4820);
+  assert_eq!(view.status, DiverStatus::Ascent);
+  assert_eq!(view.group_letter, Some(String::from("N")));
+  assert_eq!(view.stops, vec![StopCountdown { depth: 20, remaining: 840, current: false }]);
+
+  diver.handle(StationEvent::ReachedStop, 4880).unwrap();
+  let view = diver.view(5000);
+  assert_eq!(view.status, DiverStatus::Stop { depth: 20 });
+  assert_eq!(view.stops, vec![StopCountdown { depth: 20, remaining: 720, current: true }]);
+
+  diver.handle(StationEvent::LeftStop, 5720).unwrap();
+  diver.handle(StationEvent::ReachedSurface, 5760).unwrap();
+  let view = diver.view(5760 + 3600);
+  assert_eq!(view.status, DiverStatus::SurfaceInterval);
+  assert_eq!(view.timer, 3600);
+  assert!(view.stops.is_empty());
+  assert_eq!(view.group_letter, Some(String::from("M")));
+
+  // the repetitive dive carries the residual nitrogen of group M
+  diver.handle(StationEvent::LeftSurface, 5760 + 3600).unwrap();
+  let view = diver.view(5760 + 3600 + 600);
+  assert_eq!(DivePlan::from_dive(Dive::new(60, 80), 60, 60).residual_nitrogen_time(), 79);
+  assert_eq!(view.remaining_no_decompression, Some(i32::from(Dive::new(60, 0).no_decompression_limit()) - 10 - 79));
+}
+
+#[test]
+fn station_dive_below_the_no_decompression_table() {
+  let mut diver = Diver::new("Davis", 200);
+  diver.handle(StationEvent::LeftSurface, 0).unwrap();
+  diver.handle(StationEvent::ReachedBottom, 200).unwrap();
+  diver.handle(StationEvent::LeftBottom, 600).unwrap();
+
+  assert_eq!(diver.schedule().map(|schedule| schedule.max_time), Some(10));
+  let view = diver.view(620);
+  assert_eq!(view.status, DiverStatus::Ascent);
+  assert_eq!(view.group_letter, Some(String::from("H")));
+  assert_eq!(view.stops, vec![
+    StopCountdown { depth: 30, remaining: 180, current: false },
+    StopCountdown { depth: 20, remaining: 480, current: false },
+  ]);
+}
+
+#[test]
+fn station_repetitive_dive() {
+  let mut diver = Diver::new("Jones", 60);
+  diver.handle(StationEvent::LeftSurface, 0).unwrap();
+  diver.handle(StationEvent::LeftBottom, 2400).unwrap();
+  assert!(diver.schedule().is_none());
+  assert_eq!(diver.view(2410).group_letter, Some(Dive::new(60, 40).group_letter()));
+  diver.handle(StationEvent::ReachedSurface, 2520).unwrap();
+
+  let first = diver.view(2520 + 10 * 60).group_letter.unwrap();
+  let later = diver.view(2520 + 90 * 60).group_letter.unwrap();
+  assert!(later < first);
+
+  diver.set_depth(50).unwrap();
+  diver.handle(StationEvent::LeftSurface, 2520 + 90 * 60).unwrap();
+  let rnt = DivePlan::new(60, 40, 90, 50).residual_nitrogen_time();
+  let view = diver.view(2520 + 100 * 60);
+  assert_eq!(view.remaining_no_decompression, Some(i32::from(Dive::new(50, 0).no_decompression_limit()) - 10 - i32::from(rnt)));
+
+  diver.set_depth(40).unwrap();
+  let rnt = DivePlan::new(60, 40, 90, 40).residual_nitrogen_time();
+  let view = diver.view(2520 + 100 * 60);
+  assert_eq!(view.remaining_no_decompression, Some(i32::from(Dive::new(40, 0).no_decompression_limit()) - 10 - i32::from(rnt)));
+  assert_eq!(diver.set_depth(0).unwrap_err(), DiveError::InvalidDepth { depth: 0 });
+}
+
+#[test]
+fn station_unexpected_events() {
+  let mut diver = Diver::new("Brown", 40);
+  assert_eq!(diver.handle(StationEvent::ReachedBottom, 0).unwrap_err(), DiveError::UnexpectedEvent {
+    event: String::from("ReachedBottom"),
+    status: String::from("predive"),
+  });
+
+  diver.handle(StationEvent::LeftSurface, 0).unwrap();
+  diver.handle(StationEvent::LeftBottom, 600).unwrap();
+  assert_eq!(diver.handle(StationEvent::ReachedStop, 620).unwrap_err(), DiveError::UnexpectedEvent {
+    event: String::from("ReachedStop"),
+    status: String::from("ascent"),
+  });
+  assert!(diver.set_depth(60).is_err());
+}
@@ -0,0 +1,125 @@
+use crate::{Dive, DivePlan};
+use crate::advisories::AdvisoryCode;
+use crate::errors::DiveError;
+use crate::formats::{annotate, subsurface, LoggedDive, PlannedDive};
+use crate::profile::DiveProfile;
+
+const LOGBOOK: &str = r#"<divelog program='subsurface' version='3'>
+<settings></settings>
+<dives>
+<dive number='3' date='2024-05-02' time='09:00:00' duration='45:00 min'>
+  <divecomputer model='manually added dive'>
+  <depth max='9.0 m' />
+  </divecomputer>
+</dive>
+<dive number='2' date='2024-05-01' time='11:03:00' duration='21:30 min'>
+  <divecomputer model='Suunto Zoop'>
+  <sample time='1:00 min' depth='12.1 m' />
+  <sample time='20:00 min' />
+  <sample time='21:30 min' depth='0.0 m' />
+  </divecomputer>
+</dive>
+<trip date='2024-05-01' time='09:00:00' location='Pier'>
+<dive number='1' date='2024-05-01' time='09:00:00' duration='33:00 min'>
+  <divecomputer model='Suunto Zoop'>
+  <sample time='0:10 min' depth='6.0 m' />
+  <sample time='1:00 min' depth='18.2 m' />
+  <sample time='30:00 min' temp='21.0 C' />
+  <sample time='32:00 min' depth='6.0 m' />
+  <sample time='33:00 min' depth='0.0 m' />
+  </divecomputer>
+</dive>
+</trip>
+</dives>
+</divelog>"#;
+
+#[test]
+fn subsurface_import_in_time_order() {
+  let dives = subsurface::import(LOGBOOK).unwrap();
+  let numbers: Vec<&str> = dives.iter().map(|dive| dive.id.as_deref().unwrap()).collect();
+  assert_eq!(numbers, vec!["1", "2", "3"]);
+  assert_eq!(dives[0].start.unwrap().to_string(), String::from("2024-05-01T09:00:00"));
+  assert_eq!((dives[0].dive().depth, dives[0].dive().bottom_time), (60, 30));
+  assert_eq!((dives[1].dive().depth, dives[1].dive().bottom_time), (40, 20));
+  assert_eq!((dives[2].dive().depth, dives[2].dive().bottom_time), (30, 45));
+  assert_eq!(dives[0].surface_interval, None);
+  assert_eq!(dives[1].surface_interval, Some(90 * 60));
+  assert_eq!(dives[2].surface_interval, None);
+}
+
+#[test]
+fn subsurface_repetitive_groups() {
+  let dives = subsurface::annotated(LOGBOOK).unwrap();
+  let plan = DivePlan::new(60, 30, 90, 40);
+
+  let first = &dives[0].annotation;
+  assert!(first.within_no_decompression);
+  assert_eq!(first.end_group, Dive::new(60, 30).group_letter());
+  assert_eq!(first.next_group, Some(plan.repet_letter()));
+  assert_eq!(first.residual_nitrogen_time, Some(plan.residual_nitrogen_time()));
+
+  let second = &dives[1].annotation;
+  let equivalent = plan.repetitive_dive(20);
+  assert_eq!((second.equivalent_dive.depth, second.equivalent_dive.bottom_time), (equivalent.depth, equivalent.bottom_time));
+  assert_eq!(second.end_group, equivalent.group_letter());
+  assert_eq!(second.next_group, None);
+
+  let third = &dives[2].annotation;
+  assert_eq!(third.equivalent_dive.bottom_time, 45);
+  assert_eq!(third.end_group, Dive::new(30, 45).group_letter());
+}
+
+#[test]
+fn annotate_deep_dive_on_the_decompression_table() {
+  let profile = DiveProfile::planned(Dive::new(195, 10), &[]);
+  let reduction = profile.reduce().unwrap();
+  let dives = vec![LoggedDive { id: None, start: None, surface_interval: None, profile, reduction }];
+  let annotation = &annotate(&dives)[0];
+
+  assert!(!annotation.within_no_decompression);
+  assert_eq!(annotation.end_group, Dive::new(195, 10).deco_dive().repetgroup_letter);
+}
+
+#[test]
+fn annotate_repetitive_dive_after_decompression() {
+  let dives = vec![
+    PlannedDive::new(Dive::new(100, 50)).logged(),
+    PlannedDive::new(Dive::new(60, 30)).with_surface_interval(120 * 60).logged(),
+  ];
+  let annotations = annotate(&dives);
+  let plan = DivePlan::new(100, 50, 120, 60);
+
+  assert_eq!(annotations[0].end_group, Dive::new(100, 50).deco_dive().repetgroup_letter);
+  assert_eq!(annotations[0].next_group, Some(plan.repet_letter()));
+  assert!(!plan.repet_letter().is_empty());
+  assert!(plan.residual_nitrogen_time() > 0);
+  assert_eq!(annotations[0].residual_nitrogen_time, Some(plan.residual_nitrogen_time()));
+  assert!(annotations[0].advisories.is_empty());
+  assert_eq!(annotations[1].equivalent_dive.bottom_time, 30 + plan.residual_nitrogen_time());
+
+  let not_permitted = vec![
+    PlannedDive::new(Dive::new(100, 100)).logged(),
+    PlannedDive::new(Dive::new(60, 30)).with_surface_interval(120 * 60).logged(),
+  ];
+  let annotation = &annotate(&not_permitted)[0];
+  assert_eq!(annotation.next_group, Some(String::new()));
+  assert!(annotation.advisories.iter().any(|advisory| advisory.code == AdvisoryCode::RepetitiveDiveNotPermitted));
+}
+
+#[test]
+fn subsurface_import_errors() {
+  let overlapping = LOGBOOK.replace("time='11:03:00'", "time='09:20:00'");
+  assert_eq!(subsurface::import(&overlapping).unwrap_err(), DiveError::InvalidLog {
+    message: String::from("dive 2 starts before the previous dive ends"),
+  });
+
+  let bad_depth = LOGBOOK.replace("depth='12.1 m'", "depth='deep'");
+  assert_eq!(subsurface::import(&bad_depth).unwrap_err(), DiveError::InvalidLog {
+    message: String::from("'deep' is not a valid depth"),
+  });
+
+  let bad_date = LOGBOOK.replace("2024-05-02", "2024-02-30");
+  assert_eq!(subsurface::import(&bad_date).unwrap_err(), DiveError::InvalidDate {
+    text: String::from("2024-02-30 09:00:00"),
+  });
+}
@@ -0,0 +1,52 @@
+use crate::{Dive, DivePlan};
+use crate::gas::surface_supplied::{minimum_manifold_pressure, supply_check, AirBank, AirSource, SupplyDive, SupplyDiver};
+
+#[test]
+fn surface_supplied_repetitive_diver() {
+  let plan = DivePlan::new(60, 40, 120, 50);
+  let diver = SupplyDiver::from_plan(plan, 30);
+  assert_eq!(diver.dives.len(), 2);
+  assert_eq!(diver.dives[1].dive.depth, 50);
+  assert_eq!(diver.dives[1].dive.bottom_time, 30);
+  assert_eq!(diver.dives[1].schedule.bottom_time, plan.residual_nitrogen_time() + 30);
+  assert_eq!(diver.max_depth(), 60);
+}
+
+#[test]
+fn surface_supplied_repetitive_gas_volume() {
+  // the residual nitrogen time is not breathed on the supply
+  let plan = DivePlan::new(60, 40, 120, 50);
+  let repetitive = SupplyDiver::from_plan(plan, 30);
+  let source = AirSource::Compressor { capacity: 10.0 };
+  let check = supply_check(&[repetitive], 1.4, source, 135.0);
+
+  let single_dives = SupplyDiver { dives: vec![SupplyDive::single(Dive::new(60, 40)), SupplyDive::single(Dive::new(50, 30))] };
+  let expected = supply_check(&[single_dives], 1.4, source, 135.0);
+  assert!((check.required_volume - expected.required_volume).abs() < 1e-9);
+}
+
+#[test]
+fn surface_supplied_compressor() {
+  let divers = vec![SupplyDiver::from_dive(Dive::new(66, 30)), SupplyDiver::from_dive(Dive::new(33, 30))];
+  let check = supply_check(&divers, 1.4, AirSource::Compressor { capacity: 10.0 }, 135.0);
+  assert!((check.required_flow - (1.4 * 3.0 + 1.4 * 2.0)).abs() < 1e-9);
+  assert!((check.minimum_manifold_pressure - (0.445 * 66.0 + 135.0)).abs() < 1e-9);
+  assert!(check.sufficient);
+  assert!((check.margin - 3.0).abs() < 1e-9);
+}
+
+#[test]
+fn surface_supplied_bank() {
+  let divers = vec![SupplyDiver::from_dive(Dive::new(100, 50)), SupplyDiver::from_dive(Dive::new(100, 50))];
+  let mmp = minimum_manifold_pressure(100, 165.0);
+  let small = AirBank::new(1.5, 2, 3000.0);
+  let check = supply_check(&divers, 1.4, AirSource::Bank(small), 165.0);
+  assert!((check.available - (3000.0 - mmp) / 14.7 * 3.0).abs() < 1e-9);
+  assert!(!check.sufficient);
+  assert!(check.margin < 0.0);
+
+  let large = AirBank::new(1.5, 6, 3000.0);
+  let check = supply_check(&divers, 1.4, AirSource::Bank(large), 165.0);
+  assert!(check.sufficient);
+  assert!((check.margin - (check.available - check.required_volume)).abs() < 1e-9);
+}
@@ -0,0 +1,55 @@
+use crate::Dive;
+use crate::errors::DiveError;
+use crate::treatment::{run_treatment, treatment_table};
+use crate::treatment::tender::{tender_requirements, ChamberSession, TenderDive};
+
+#[test]
+fn tender_of_treatment_table() {
+  let schedule = run_treatment(&treatment_table("TT6").unwrap(), &[]).unwrap();
+  let requirement = tender_requirements(&ChamberSession::Treatment(schedule), &[]).unwrap();
+  assert_eq!(requirement.total_o2_time, 60);
+  assert_eq!(requirement.repet_group, None);
+  assert_eq!(requirement.no_dive_hours, 18);
+}
+
+#[test]
+fn tender_of_treatment_table_after_a_dive() {
+  let schedule = run_treatment(&treatment_table("TT5").unwrap(), &[]).unwrap();
+  let recent = TenderDive { dive: Dive::new(60, 40), surface_interval_time: 120 };
+  let requirement = tender_requirements(&ChamberSession::Treatment(schedule), &[recent]).unwrap();
+  assert_eq!(requirement.total_o2_time, 50);
+  assert_eq!((requirement.o2_breathing[0].start, requirement.o2_breathing[0].end), (85, 105));
+  assert_eq!(requirement.o2_breathing[0].start_depth, 30);
+
+  let old = TenderDive { dive: Dive::new(60, 40), surface_interval_time: 20 * 60 };
+  let schedule = run_treatment(&treatment_table("TT6").unwrap(), &[]).unwrap();
+  let requirement = tender_requirements(&ChamberSession::Treatment(schedule.clone()), &[old]).unwrap();
+  assert_eq!(requirement.total_o2_time, 60);
+  let recent = TenderDive { dive: Dive::new(60, 40), surface_interval_time: 120 };
+  let requirement = tender_requirements(&ChamberSession::Treatment(schedule), &[old, recent]).unwrap();
+  assert_eq!(requirement.total_o2_time, 120);
+}
+
+#[test]
+fn tender_of_surdo2_session() {
+  let requirement = tender_requirements(&ChamberSession::SurDO2 { o2cp: 1.0 }, &[]).unwrap();
+  assert_eq!(requirement.total_o2_time, 15);
+  assert_eq!((requirement.equivalent_dive.depth, requirement.equivalent_dive.bottom_time), (50, 30));
+  assert_eq!(requirement.repet_group, Some(Dive::new(50, 30).group_letter()));
+  assert_eq!(requirement.no_dive_hours, 0);
+
+  let recent = TenderDive { dive: Dive::new(60, 40), surface_interval_time: 60 };
+  let requirement = tender_requirements(&ChamberSession::SurDO2 { o2cp: 1.0 }, &[recent]).unwrap();
+  assert_eq!(requirement.total_o2_time, 30);
+  assert!(requirement.equivalent_dive.bottom_time > 30);
+}
+
+#[test]
+fn tender_of_unknown_treatment_table() {
+  let mut schedule = run_treatment(&treatment_table("TT6").unwrap(), &[]).unwrap();
+  schedule.code = String::from("TT8");
+  assert_eq!(
+    tender_requirements(&ChamberSession::Treatment(schedule), &[]).unwrap_err(),
+    DiveError::UnknownTreatmentTable { code: String::from("TT8") }
+  );
+}
@@ -0,0 +1,69 @@
+use crate::errors::DiveError;
+use crate::timekeeping::{CalendarDate, ClockTime, DateTime, DiveRecord};
+
+fn clock(text: &str) -> ClockTime {
+  text.parse().unwrap()
+}
+
+#[test]
+fn clock_time_parsing() {
+  assert_eq!(clock("09:05"), ClockTime::new(9, 5, 0).unwrap());
+  assert_eq!(clock("23:59:30").seconds(), 86370);
+  assert_eq!(clock("7:3:9").to_string(), String::from("07:03:09"));
+  for text in ["24:00", "12:60", "12", "12:30:00:00", "ab:cd", "12:"].iter() {
+    assert_eq!(text.parse::<ClockTime>().unwrap_err(), DiveError::InvalidClockTime { text: String::from(*text) });
+  }
+}
+
+#[test]
+fn dive_record_times() {
+  let record = DiveRecord::new(60, clock("10:00:00"), clock("10:01:30"), clock("10:39:10"), clock("10:41:10")).unwrap();
+  assert_eq!(record.descent_time(), 90);
+  assert_eq!(record.bottom_time(), 40);
+  assert_eq!(record.ascent_time(), 120);
+  assert_eq!(record.total_dive_time(), 2470);
+  let dive = record.dive();
+  assert_eq!((dive.depth, dive.bottom_time), (60, 40));
+  assert_eq!(dive.group_letter(), String::from("H"));
+}
+
+#[test]
+fn dive_record_across_midnight() {
+  let record = DiveRecord::new(40, clock("23:50"), clock("23:51"), clock("00:20"), clock("00:22")).unwrap();
+  assert_eq!(record.bottom_time(), 30);
+  assert_eq!(record.total_dive_time(), 32 * 60);
+}
+
+#[test]
+fn dive_record_unordered_times() {
+  assert_eq!(
+    DiveRecord::new(40, clock("10:00"), clock("10:30"), clock("10:20"), clock("10:40")).unwrap_err(),
+    DiveError::UnorderedClockTimes
+  );
+}
+
+#[test]
+fn calendar_dates() {
+  let date: CalendarDate = "2000-03-01".parse().unwrap();
+  assert_eq!(date.days(), 11017);
+  assert_eq!("1970-01-01".parse::<CalendarDate>().unwrap().days(), 0);
+  assert_eq!("1969-12-31".parse::<CalendarDate>().unwrap().days(), -1);
+  assert_eq!("2024-02-29".parse::<CalendarDate>().unwrap().to_string(), String::from("2024-02-29"));
+  for text in ["2023-02-29", "2024-13-01", "24-01-01", "2024-01", "2024-01-00"].iter() {
+    assert_eq!(text.parse::<CalendarDate>().unwrap_err(), DiveError::InvalidDate { text: String::from(*text) });
+  }
+
+  let start: DateTime = "2024-05-01T23:30".parse().unwrap();
+  let end: DateTime = "2024-05-02 01:00:30".parse().unwrap();
+  assert_eq!(end.seconds() - start.seconds(), 5430);
+  assert_eq!(start.to_string(), String::from("2024-05-01T23:30:00"));
+}
+
+#[test]
+fn date_time_from_seconds() {
+  for text in ["1970-01-01T00:00:00", "2000-02-29T12:34:56", "2024-12-31T23:59:59", "1969-07-20T20:17:40"].iter() {
+    let date_time: DateTime = text.parse().unwrap();
+    assert_eq!(DateTime::from_seconds(date_time.seconds()), date_time);
+  }
+  assert_eq!(CalendarDate::from_days(11017), CalendarDate::new(2000, 3, 1).unwrap());
+}
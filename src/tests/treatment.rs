@@ -0,0 +1,90 @@
+use crate::airtables::{self, TreatmentGas};
+use crate::errors::DiveError;
+use crate::gas::oxygen_supply::{OxygenQuantity, OxygenUse};
+use crate::treatment::{run_treatment, treatment_table, ExtensionRequest};
+
+#[test]
+fn treatment_tables_are_embedded() {
+  let tables = airtables::treatment_tables().unwrap();
+  let codes: Vec<String> = tables.table_data.iter().map(|table| table.code.clone()).collect();
+  assert_eq!(codes, vec!["TT5", "TT6", "TT6A", "TT4", "TT7", "TT9"]);
+  assert!(treatment_table("TT8").is_none());
+}
+
+#[test]
+fn treatment_table_5_schedule() {
+  let schedule = run_treatment(&treatment_table("TT5").unwrap(), &[]).unwrap();
+  assert_eq!(schedule.total_time, 135);
+  assert_eq!(schedule.timeline[0].gas, TreatmentGas::Oxygen);
+  assert_eq!(schedule.tender_o2.len(), 1);
+  assert_eq!((schedule.tender_o2[0].start, schedule.tender_o2[0].end), (105, 135));
+}
+
+#[test]
+fn treatment_table_6_extensions() {
+  let table = treatment_table("TT6").unwrap();
+  let schedule = run_treatment(&table, &[]).unwrap();
+  assert_eq!(schedule.total_time, 285);
+  assert_eq!(schedule.tender_o2[0].end - schedule.tender_o2[0].start, 30);
+
+  let extended = run_treatment(&table, &[
+    ExtensionRequest { depth: 60, count: 1 },
+    ExtensionRequest { depth: 30, count: 1 },
+  ]).unwrap();
+  assert_eq!(extended.total_time, 285 + 25 + 75);
+  assert_eq!(extended.timeline.iter().filter(|entry| entry.extension).count(), 4);
+  assert_eq!(extended.timeline[6].start_depth, 60);
+  assert!(extended.timeline[6].extension);
+  assert_eq!(extended.tender_o2[0].end - extended.tender_o2[0].start, 60);
+
+  assert_eq!(
+    run_treatment(&table, &[ExtensionRequest { depth: 30, count: 3 }]).unwrap_err(),
+    DiveError::ExtensionNotAllowed { table: String::from("TT6"), depth: 30, count: 3, max_count: 2 }
+  );
+}
+
+#[test]
+fn treatment_extensions_per_depth() {
+  let table = treatment_table("TT6").unwrap();
+  assert_eq!(
+    run_treatment(&table, &[ExtensionRequest { depth: 30, count: 2 }, ExtensionRequest { depth: 30, count: 2 }]).unwrap_err(),
+    DiveError::ExtensionNotAllowed { table: String::from("TT6"), depth: 30, count: 4, max_count: 2 }
+  );
+  assert_eq!(
+    run_treatment(&table, &[ExtensionRequest { depth: 30, count: 200 }, ExtensionRequest { depth: 30, count: 200 }]).unwrap_err(),
+    DiveError::ExtensionNotAllowed { table: String::from("TT6"), depth: 30, count: 255, max_count: 2 }
+  );
+  assert_eq!(
+    run_treatment(&table, &[ExtensionRequest { depth: 45, count: 0 }]).unwrap_err(),
+    DiveError::ExtensionNotAllowed { table: String::from("TT6"), depth: 45, count: 0, max_count: 0 }
+  );
+
+  let split = run_treatment(&table, &[ExtensionRequest { depth: 30, count: 1 }, ExtensionRequest { depth: 30, count: 1 }]).unwrap();
+  assert_eq!(split.extensions, 2);
+  assert_eq!(split.total_time, run_treatment(&table, &[ExtensionRequest { depth: 30, count: 2 }]).unwrap().total_time);
+}
+
+#[test]
+fn treatment_oxygen_quantity() {
+  let schedule = run_treatment(&treatment_table("TT9").unwrap(), &[]).unwrap();
+  let mut quantity = OxygenQuantity::new(1.5, 1.0);
+  quantity.add_treatment(&schedule);
+  let patient: u16 = quantity.demand.iter()
+    .filter(|period| period.usage == OxygenUse::ChamberDiver)
+    .map(|period| period.minutes)
+    .sum();
+  assert_eq!(patient, 92);
+  assert!(quantity.demand.iter().any(|period| period.usage == OxygenUse::ChamberTender));
+}
+
+#[test]
+fn treatment_oxygen_quantity_air_or_oxygen() {
+  let schedule = run_treatment(&treatment_table("TT7").unwrap(), &[]).unwrap();
+  let mut quantity = OxygenQuantity::new(1.5, 1.0);
+  quantity.add_treatment(&schedule);
+  let patient: u16 = quantity.demand.iter()
+    .filter(|period| period.usage == OxygenUse::ChamberDiver)
+    .map(|period| period.minutes)
+    .sum();
+  assert_eq!(patient, 720 + 200 + 300 + 480 + 240 + 1);
+}
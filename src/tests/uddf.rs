@@ -0,0 +1,113 @@
+use crate::{Dive, DivePlan};
+use crate::errors::DiveError;
+use crate::formats::{dive_plans, uddf, PlannedDive};
+
+const LOG: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<uddf xmlns="http://www.streit.cc/uddf/3.2/" version="3.2.0">
+  <profiledata>
+    <repetitiongroup id="rg1">
+      <dive id="d1">
+        <informationbeforedive>
+          <surfaceintervalbeforedive><infinity/></surfaceintervalbeforedive>
+        </informationbeforedive>
+        <samples>
+          <waypoint><depth>0.0</depth><divetime>0</divetime></waypoint>
+          <waypoint><depth>18.288</depth><divetime>60</divetime></waypoint>
+          <waypoint><depth>18.0</depth><divetime>900</divetime></waypoint>
+          <waypoint><depth>18.288</depth><divetime>1800</divetime></waypoint>
+          <waypoint><depth>6.096</depth><divetime>2040</divetime></waypoint>
+          <waypoint><depth>0.0</depth><divetime>2100</divetime></waypoint>
+        </samples>
+      </dive>
+      <dive id="d2">
+        <informationbeforedive>
+          <surfaceintervalbeforedive><passedtime>5430</passedtime></surfaceintervalbeforedive>
+        </informationbeforedive>
+        <samples>
+          <waypoint><depth>0.0</depth><divetime>0</divetime></waypoint>
+          <waypoint><depth>12.192</depth><divetime>60</divetime></waypoint>
+          <waypoint><depth>12.192</depth><divetime>1200</divetime></waypoint>
+          <waypoint><depth>0.0</depth><divetime>1290</divetime></waypoint>
+        </samples>
+      </dive>
+    </repetitiongroup>
+    <repetitiongroup id="rg2">
+      <dive id="d3">
+        <samples>
+          <waypoint><depth>0.0</depth><divetime>0</divetime></waypoint>
+          <waypoint><depth>9.144</depth><divetime>60</divetime></waypoint>
+          <waypoint><depth>0.0</depth><divetime>600</divetime></waypoint>
+        </samples>
+      </dive>
+    </repetitiongroup>
+  </profiledata>
+</uddf>"#;
+
+#[test]
+fn uddf_import_dives() {
+  let dives = uddf::import(LOG).unwrap();
+  assert_eq!(dives.len(), 3);
+  assert_eq!(dives[0].id.as_deref(), Some("d1"));
+  assert_eq!(dives[0].surface_interval, None);
+  assert_eq!((dives[0].dive().depth, dives[0].dive().bottom_time), (60, 30));
+  assert_eq!(dives[1].surface_interval, Some(5430));
+  assert_eq!((dives[1].dive().depth, dives[1].dive().bottom_time), (40, 20));
+  assert_eq!(dives[1].reduction.reach_surface, Some(1290));
+  assert_eq!(dives[2].surface_interval, None);
+}
+
+#[test]
+fn uddf_dive_plan_chain() {
+  let dives = uddf::import(LOG).unwrap();
+  let plans = dive_plans(&dives);
+  assert_eq!(plans.len(), 2);
+  assert!(plans[1].is_none());
+
+  let plan = plans[0].unwrap();
+  let expected = DivePlan::new(60, 30, 90, 40);
+  assert_eq!((plan.depth, plan.bottom_time, plan.surface_interval_time, plan.next_dive_depth), (60, 30, 90, 40));
+  assert_eq!(plan.repet_letter(), expected.repet_letter());
+  assert_eq!(plan.residual_nitrogen_time(), expected.residual_nitrogen_time());
+}
+
+#[test]
+fn uddf_import_errors() {
+  let missing_time = LOG.replacen("<divetime>60</divetime>", "", 1);
+  assert_eq!(uddf::import(&missing_time).unwrap_err(), DiveError::InvalidLog {
+    message: String::from("<waypoint> has no <divetime> element"),
+  });
+
+  let bad_depth = LOG.replacen("18.288", "deep", 1);
+  assert_eq!(uddf::import(&bad_depth).unwrap_err(), DiveError::InvalidLog {
+    message: String::from("<depth> holds 'deep' instead of a number"),
+  });
+
+  assert!(matches!(uddf::import("<divelog/>"), Err(DiveError::InvalidLog { .. })));
+  assert!(matches!(uddf::import("<uddf>"), Err(DiveError::InvalidLog { .. })));
+}
+
+#[test]
+fn uddf_export_round_trip() {
+  let planned = vec![
+    PlannedDive::new(Dive::new(60, 80)),
+    PlannedDive::new(Dive::new(40, 20)).with_surface_interval(5400),
+    PlannedDive::new(Dive::new(30, 30)),
+  ];
+  let xml = uddf::export(&planned);
+  assert!(xml.contains("<repetitiongroup id=\"rg2\">"));
+  assert!(xml.contains("<decostop kind=\"mandatory\" decodepth=\"6.096\" duration=\"840\"/>"));
+  assert!(xml.contains("<diveduration>5760</diveduration>"));
+
+  let dives = uddf::import(&xml).unwrap();
+  assert_eq!(dives.len(), 3);
+  for (planned, logged) in planned.iter().zip(dives.iter()) {
+    assert_eq!((logged.dive().depth, logged.dive().bottom_time), (planned.dive.depth, planned.dive.bottom_time));
+    assert_eq!(logged.surface_interval, planned.surface_interval);
+    assert_eq!(logged.profile, planned.profile());
+  }
+  assert_eq!(dives[0].reduction.reach_surface, Some(5760));
+
+  let plans = dive_plans(&dives);
+  assert_eq!(plans[0].unwrap().surface_interval_time, 90);
+  assert!(plans[1].is_none());
+}
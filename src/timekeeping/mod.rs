@@ -0,0 +1,305 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Dive, ExposureModifier};
+use crate::errors::DiveError;
+use crate::profile::minutes;
+
+/// seconds in a day
+const DAY: u32 = 24 * 60 * 60;
+
+/// a time of the day as read on the timekeeper's watch
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ClockTime {
+  /// hour of the day from 0 to 23
+  pub hour: u8,
+  /// minute of the hour from 0 to 59
+  pub minute: u8,
+  /// second of the minute from 0 to 59
+  pub second: u8,
+}
+
+/// a calendar date of the Gregorian calendar
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CalendarDate {
+  /// year of the date
+  pub year: u16,
+  /// month of the year from 1 to 12
+  pub month: u8,
+  /// day of the month from 1 to 31
+  pub day: u8,
+}
+
+/// a calendar date and a clock time, such as the start of a logged dive
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DateTime {
+  /// the calendar date
+  pub date: CalendarDate,
+  /// the clock time of the day
+  pub time: ClockTime,
+}
+
+/// the clock times of a dive as recorded in the dive log
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
+pub struct DiveRecord {
+  /// depth of the dive expressed in feet of sea water
+  pub depth: u16,
+  /// clock time when the diver left the surface (LS)
+  pub left_surface: ClockTime,
+  /// clock time when the diver reached the bottom (RB)
+  pub reached_bottom: ClockTime,
+  /// clock time when the diver left the bottom (LB)
+  pub left_bottom: ClockTime,
+  /// clock time when the diver reached the surface (RS)
+  pub reached_surface: ClockTime,
+  /// conditions of the dive that require the next longer bottom time schedule
+  #[serde(default)]
+  pub exposure: ExposureModifier,
+}
+
+impl ClockTime {
+  pub fn new(hour: u8, minute: u8, second: u8) -> Result<Self, DiveError> {
+    //! Instantiates a new ClockTime object
+    if hour > 23 || minute > 59 || second > 59 {
+      return Err(DiveError::InvalidClockTime {
+        text: format!("{:02}:{:02}:{:02}", hour, minute, second),
+      });
+    }
+
+    Ok(Self {
+      hour,
+      minute,
+      second,
+    })
+  }
+
+  pub fn seconds(self) -> u32 {
+    //! returns the seconds elapsed since midnight
+    u32::from(self.hour) * 3600 + u32::from(self.minute) * 60 + u32::from(self.second)
+  }
+
+  pub fn seconds_until(self, later: ClockTime) -> u32 {
+    //! returns the seconds elapsed from this clock time to a later one,
+    //! crossing midnight when the later clock time reads earlier
+    (later.seconds() + DAY - self.seconds()) % DAY
+  }
+}
+
+impl FromStr for ClockTime {
+  type Err = DiveError;
+
+  fn from_str(text: &str) -> Result<Self, Self::Err> {
+    //! parses a clock time written as HH:MM or HH:MM:SS
+    let invalid = || DiveError::InvalidClockTime { text: String::from(text) };
+    let fields: Vec<&str> = text.trim().split(':').collect();
+
+    if fields.len() < 2 || fields.len() > 3 || fields.iter().any(|field| field.is_empty() || field.len() > 2) {
+      return Err(invalid());
+    }
+
+    let mut values = [0u8; 3];
+    for (value, field) in values.iter_mut().zip(fields.iter()) {
+      *value = field.parse().map_err(|_| invalid())?;
+    }
+
+    ClockTime::new(values[0], values[1], values[2]).map_err(|_| invalid())
+  }
+}
+
+impl fmt::Display for ClockTime {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)
+  }
+}
+
+impl CalendarDate {
+  pub fn new(year: u16, month: u8, day: u8) -> Result<Self, DiveError> {
+    //! Instantiates a new CalendarDate object
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+      1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+      4 | 6 | 9 | 11 => 30,
+      2 if leap => 29,
+      2 => 28,
+      _ => 0,
+    };
+    if day == 0 || day > days_in_month {
+      return Err(DiveError::InvalidDate {
+        text: format!("{:04}-{:02}-{:02}", year, month, day),
+      });
+    }
+
+    Ok(Self {
+      year,
+      month,
+      day,
+    })
+  }
+
+  pub fn days(self) -> i64 {
+    //! returns the days elapsed since 1970-01-01, negative before it
+    let year = i64::from(self.year) - if self.month <= 2 { 1 } else { 0 };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(self.month);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(self.day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+  }
+
+  pub fn from_days(days: i64) -> Self {
+    //! returns the calendar date the given days after 1970-01-01
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    Self {
+      year: year as u16,
+      month: month as u8,
+      day: day as u8,
+    }
+  }
+}
+
+impl FromStr for CalendarDate {
+  type Err = DiveError;
+
+  fn from_str(text: &str) -> Result<Self, Self::Err> {
+    //! parses a calendar date written as YYYY-MM-DD
+    let invalid = || DiveError::InvalidDate { text: String::from(text) };
+    let fields: Vec<&str> = text.trim().split('-').collect();
+
+    if fields.len() != 3 || fields[0].len() != 4 || fields[1..].iter().any(|field| field.is_empty() || field.len() > 2) {
+      return Err(invalid());
+    }
+
+    let year = fields[0].parse().map_err(|_| invalid())?;
+    let month = fields[1].parse().map_err(|_| invalid())?;
+    let day = fields[2].parse().map_err(|_| invalid())?;
+    CalendarDate::new(year, month, day).map_err(|_| invalid())
+  }
+}
+
+impl fmt::Display for CalendarDate {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+  }
+}
+
+impl DateTime {
+  pub fn new(date: CalendarDate, time: ClockTime) -> Self {
+    //! Instantiates a new DateTime object
+    Self {
+      date,
+      time,
+    }
+  }
+
+  pub fn seconds(self) -> i64 {
+    //! returns the seconds elapsed since 1970-01-01 00:00:00
+    self.date.days() * i64::from(DAY) + i64::from(self.time.seconds())
+  }
+
+  pub fn from_seconds(seconds: i64) -> Self {
+    //! returns the date and time the given seconds after 1970-01-01 00:00:00
+    let day = i64::from(DAY);
+    let time = seconds.rem_euclid(day) as u32;
+
+    Self {
+      date: CalendarDate::from_days(seconds.div_euclid(day)),
+      time: ClockTime {
+        hour: (time / 3600) as u8,
+        minute: (time / 60 % 60) as u8,
+        second: (time % 60) as u8,
+      },
+    }
+  }
+}
+
+impl FromStr for DateTime {
+  type Err = DiveError;
+
+  fn from_str(text: &str) -> Result<Self, Self::Err> {
+    //! parses a date and time written as YYYY-MM-DDTHH:MM[:SS], the date
+    //! and the time may also be separated by a space
+    let invalid = || DiveError::InvalidDate { text: String::from(text) };
+    let mut fields = text.trim().splitn(2, ['T', ' ']);
+    let date = fields.next().ok_or_else(invalid)?.parse().map_err(|_| invalid())?;
+    let time = fields.next().ok_or_else(invalid)?.parse().map_err(|_| invalid())?;
+    Ok(DateTime::new(date, time))
+  }
+}
+
+impl fmt::Display for DateTime {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}T{}", self.date, self.time)
+  }
+}
+
+impl DiveRecord {
+  pub fn new(depth: u16, left_surface: ClockTime, reached_bottom: ClockTime, left_bottom: ClockTime, reached_surface: ClockTime) -> Result<Self, DiveError> {
+    //! Instantiates a new DiveRecord object from the clock times of the
+    //! dive. the clock times must follow each other within 24 hours and
+    //! may cross midnight
+    let record = Self {
+      depth,
+      left_surface,
+      reached_bottom,
+      left_bottom,
+      reached_surface,
+      exposure: ExposureModifier::None,
+    };
+
+    let elapsed = left_surface.seconds_until(reached_bottom)
+      + reached_bottom.seconds_until(left_bottom)
+      + left_bottom.seconds_until(reached_surface);
+    if elapsed >= DAY {
+      return Err(DiveError::UnorderedClockTimes);
+    }
+
+    Ok(record)
+  }
+
+  pub fn with_exposure(self, exposure: ExposureModifier) -> Self {
+    //! returns the DiveRecord object with the given exposure modifier
+    Self {
+      exposure,
+      ..self
+    }
+  }
+
+  pub fn descent_time(self) -> u32 {
+    //! returns the time from leaving the surface to reaching the bottom
+    //! expressed in seconds
+    self.left_surface.seconds_until(self.reached_bottom)
+  }
+
+  pub fn bottom_time(self) -> u16 {
+    //! returns the time from leaving the surface to leaving the bottom
+    //! rounded up to the next whole minute
+    minutes(self.left_surface.seconds_until(self.left_bottom))
+  }
+
+  pub fn ascent_time(self) -> u32 {
+    //! returns the time from leaving the bottom to reaching the surface
+    //! expressed in seconds
+    self.left_bottom.seconds_until(self.reached_surface)
+  }
+
+  pub fn total_dive_time(self) -> u32 {
+    //! returns the time from leaving the surface to reaching the surface
+    //! expressed in seconds
+    self.left_surface.seconds_until(self.reached_surface)
+  }
+
+  pub fn dive(self) -> Dive {
+    //! returns the Dive object of the record
+    Dive::new(self.depth, self.bottom_time()).with_exposure(self.exposure)
+  }
+}
@@ -0,0 +1,163 @@
+use crate::airtables::{self, TreatmentGas, TreatmentStep, TreatmentTable};
+use crate::errors::DiveError;
+
+/// this module provides the requirements of the inside tender of a chamber session
+pub mod tender;
+
+/// extensions requested at a depth of a treatment table
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ExtensionRequest {
+  /// depth of the extensions expressed in feet of sea water
+  pub depth: u16,
+  /// number of extensions
+  pub count: u8,
+}
+
+/// an entry of the timeline of a treatment
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
+pub struct TimelineEntry {
+  /// elapsed time at the beginning of the entry expressed in minutes
+  pub start: u16,
+  /// elapsed time at the end of the entry expressed in minutes
+  pub end: u16,
+  /// depth at the beginning of the entry expressed in feet of sea water
+  pub start_depth: u16,
+  /// depth at the end of the entry expressed in feet of sea water
+  pub end_depth: u16,
+  /// breathing gas during the entry
+  pub gas: TreatmentGas,
+  /// the entry belongs to an extension of the table
+  pub extension: bool,
+}
+
+/// the generated schedule of a recompression treatment
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TreatmentSchedule {
+  /// short code of the treatment table
+  pub code: String,
+  /// oficially recognized name of the treatment table
+  pub name: String,
+  /// the timeline the chamber operator follows, from reaching the
+  /// treatment depth to surfacing
+  pub timeline: Vec<TimelineEntry>,
+  /// number of extensions included in the timeline
+  pub extensions: u8,
+  /// total time of the treatment expressed in minutes
+  pub total_time: u16,
+  /// the oxygen breathing periods of the inside tender
+  pub tender_o2: Vec<TimelineEntry>,
+}
+
+/// treatment_table() returns the treatment table with the given code
+/// (e.g TT6) if it exists
+pub fn treatment_table(code: &str) -> Option<TreatmentTable> {
+  let treatment_tables = airtables::treatment_tables()
+    .expect("Error deserializing treatment tables");
+
+  treatment_tables.table_data.into_iter().find(|table| table.code == code)
+}
+
+/// run_treatment() returns the schedule of a treatment table with the
+/// requested extensions inserted where the table allows them. the requests
+/// are summed per depth and every depth must be one the table extends
+pub fn run_treatment(table: &TreatmentTable, extensions: &[ExtensionRequest]) -> Result<TreatmentSchedule, DiveError> {
+  let mut requested: Vec<ExtensionRequest> = vec![];
+  for request in extensions.iter() {
+    match requested.iter_mut().find(|total| total.depth == request.depth) {
+      Some(total) => total.count = total.count.saturating_add(request.count),
+      None => requested.push(*request),
+    }
+  }
+
+  let mut extension_count: u8 = 0;
+  for request in requested.iter() {
+    let allowed = table.extensions.iter()
+      .find(|extension| extension.depth == request.depth)
+      .map(|extension| extension.max_count);
+
+    let not_allowed = || DiveError::ExtensionNotAllowed {
+      table: table.code.clone(),
+      depth: request.depth,
+      count: request.count,
+      max_count: allowed.unwrap_or(0),
+    };
+    if !matches!(allowed, Some(max_count) if request.count <= max_count) {
+      return Err(not_allowed());
+    }
+    extension_count = extension_count.checked_add(request.count).ok_or_else(not_allowed)?;
+  }
+
+  let mut steps: Vec<(TreatmentStep, bool)> = vec![];
+  for (index, step) in table.steps.iter().enumerate() {
+    steps.push((*step, false));
+
+    for extension in table.extensions.iter().filter(|extension| extension.after_step == index) {
+      let count = requested.iter()
+        .find(|request| request.depth == extension.depth)
+        .map_or(0, |request| request.count);
+
+      for _ in 0..count {
+        steps.extend(extension.steps.iter().map(|step| (*step, true)));
+      }
+    }
+  }
+
+  let mut elapsed = 0;
+  let timeline: Vec<TimelineEntry> = steps.iter()
+    .map(|(step, extension)| {
+      let entry = TimelineEntry {
+        start: elapsed,
+        end: elapsed + step.time,
+        start_depth: step.start_depth,
+        end_depth: step.end_depth,
+        gas: step.gas,
+        extension: *extension,
+      };
+      elapsed = entry.end;
+      entry
+    })
+    .collect();
+
+  Ok(TreatmentSchedule {
+    code: table.code.clone(),
+    name: table.name.clone(),
+    tender_o2: tender_o2(table, &timeline, extension_count, 0),
+    timeline,
+    extensions: extension_count,
+    total_time: elapsed,
+  })
+}
+
+/// returns the oxygen breathing periods of the inside tender at the end of
+/// the last stop, extended by the additional minutes, and during the final
+/// ascent of a treatment timeline
+pub(crate) fn tender_o2(table: &TreatmentTable, timeline: &[TimelineEntry], extensions: u8, additional: u16) -> Vec<TimelineEntry> {
+  let mut periods = vec![];
+  let final_ascent = match timeline.last() {
+    Some(entry) => *entry,
+    None => return periods,
+  };
+
+  let rule = table.tender_o2;
+  let minutes = additional + if extensions > rule.extension_threshold { rule.extended_minutes } else { rule.minutes };
+
+  if minutes > 0 {
+    periods.push(TimelineEntry {
+      start: final_ascent.start.saturating_sub(minutes),
+      end: final_ascent.start,
+      start_depth: rule.depth,
+      end_depth: rule.depth,
+      gas: TreatmentGas::Oxygen,
+      extension: false,
+    });
+  }
+  if rule.final_ascent {
+    periods.push(TimelineEntry {
+      gas: TreatmentGas::Oxygen,
+      extension: false,
+      ..final_ascent
+    });
+  }
+
+  periods
+}
@@ -0,0 +1,126 @@
+use crate::{Dive, DivePlan};
+use crate::airtables::TreatmentGas;
+use crate::errors::DiveError;
+use crate::oxygen::{chamber_stops, tender_o2_minutes};
+use crate::treatment::{tender_o2, treatment_table, TimelineEntry, TreatmentSchedule};
+
+/// surface interval after which a previous dive of the tender is no longer
+/// accounted expressed in minutes
+pub const REPETITIVE_WINDOW: u16 = 18 * 60;
+
+/// additional oxygen breathed by a tender of a SurDO2 session who made a
+/// dive within the REPETITIVE_WINDOW expressed in minutes
+pub const SURDO2_REPETITIVE_MINUTES: u16 = 15;
+
+/// air break between two SurDO2 chamber periods expressed in minutes
+pub const SURDO2_AIR_BREAK: u16 = 5;
+
+/// a chamber session attended by an inside tender
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "session", rename_all = "snake_case")]
+pub enum ChamberSession {
+  /// a recompression treatment
+  Treatment(TreatmentSchedule),
+  /// a SurDO2 session with the given number of chamber periods
+  SurDO2 {
+    /// number of chamber periods
+    o2cp: f32,
+  },
+}
+
+/// a recent dive of the inside tender
+#[derive(Serialize, Deserialize, Copy, Clone, Debug)]
+pub struct TenderDive {
+  /// the dive made by the tender
+  pub dive: Dive,
+  /// time from surfacing to entering the chamber expressed in minutes
+  pub surface_interval_time: u16,
+}
+
+/// the requirements of the inside tender of a chamber session
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TenderRequirement {
+  /// the oxygen breathing periods of the tender on the session timeline
+  pub o2_breathing: Vec<TimelineEntry>,
+  /// total oxygen breathing time expressed in minutes
+  pub total_o2_time: u16,
+  /// the chamber exposure of the tender as an air dive, with the residual
+  /// nitrogen time of a recent dive added to its bottom time
+  pub equivalent_dive: Dive,
+  /// the repetitive group of the tender after the session, none when the
+  /// exposure is beyond the no-decompression table
+  pub repet_group: Option<String>,
+  /// hours the tender must wait before diving again
+  pub no_dive_hours: u16,
+}
+
+/// tender_requirements() returns the oxygen breathing and repetitive diving
+/// restrictions of the inside tender of a chamber session. the most recent
+/// dive of the tender made within the REPETITIVE_WINDOW adds its residual
+/// nitrogen time to the chamber exposure and extends the oxygen breathing.
+/// a tender left without a repetitive group must not dive for 18 hours.
+/// a treatment schedule must name one of the embedded treatment tables
+pub fn tender_requirements(session: &ChamberSession, recent_dives: &[TenderDive]) -> Result<TenderRequirement, DiveError> {
+  let recent = recent_dives.iter()
+    .filter(|recent| recent.surface_interval_time < REPETITIVE_WINDOW)
+    .min_by_key(|recent| recent.surface_interval_time);
+
+  let (o2_breathing, chamber_dive) = match session {
+    ChamberSession::Treatment(schedule) => {
+      let table = treatment_table(&schedule.code)
+        .ok_or_else(|| DiveError::UnknownTreatmentTable { code: schedule.code.clone() })?;
+      let additional = if recent.is_some() { table.tender_o2.repetitive_minutes } else { 0 };
+      let final_ascent = schedule.timeline.last().map_or(0, |entry| entry.start);
+      let depth = schedule.timeline.iter().map(|entry| entry.start_depth).max().unwrap_or(0);
+      (
+        tender_o2(&table, &schedule.timeline, schedule.extensions, additional),
+        Dive::new(depth, final_ascent),
+      )
+    }
+    ChamberSession::SurDO2 { o2cp } => {
+      let additional = if recent.is_some() { SURDO2_REPETITIVE_MINUTES } else { 0 };
+      surdo2_tender(*o2cp, additional)
+    }
+  };
+
+  let equivalent_dive = match recent {
+    Some(recent) => {
+      let plan = DivePlan::from_dive(recent.dive, recent.surface_interval_time, chamber_dive.depth);
+      Dive::new(chamber_dive.depth, chamber_dive.bottom_time.saturating_add(plan.residual_nitrogen_time()))
+    }
+    None => chamber_dive,
+  };
+
+  let repet_group = Some(equivalent_dive.group_letter())
+    .filter(|letter| letter.len() == 1);
+
+  Ok(TenderRequirement {
+    total_o2_time: o2_breathing.iter().map(|entry| entry.end - entry.start).sum(),
+    o2_breathing,
+    equivalent_dive,
+    no_dive_hours: if repet_group.is_some() { 0 } else { 18 },
+    repet_group,
+  })
+}
+
+/// returns the oxygen breathing of the tender, extended by the additional
+/// minutes, and the chamber exposure of a SurDO2 session
+fn surdo2_tender(o2cp: f32, additional: u16) -> (Vec<TimelineEntry>, Dive) {
+  let stops = chamber_stops(o2cp);
+  let air_breaks = (o2cp.ceil() as u16).saturating_sub(1) * SURDO2_AIR_BREAK;
+  let session_time = stops.iter().map(|stop| stop.time).sum::<u16>() + air_breaks;
+  let minutes = tender_o2_minutes(o2cp) + additional;
+  let depth = stops.last().map_or(0, |stop| stop.depth);
+
+  let o2_breathing = vec![TimelineEntry {
+    start: session_time.saturating_sub(minutes),
+    end: session_time,
+    start_depth: depth,
+    end_depth: depth,
+    gas: TreatmentGas::Oxygen,
+    extension: false,
+  }];
+  let max_depth = stops.iter().map(|stop| stop.depth).max().unwrap_or(0);
+
+  (o2_breathing, Dive::new(max_depth, session_time))
+}
@@ -0,0 +1,15 @@
+/// meters in a foot, used to convert depths logged in meters of sea water
+/// to the feet of sea water of the tables
+pub const METERS_PER_FOOT: f64 = 0.3048;
+
+/// meters_to_feet() converts a depth expressed in meters of sea water to
+/// feet of sea water
+pub fn meters_to_feet(meters: f64) -> f64 {
+  meters / METERS_PER_FOOT
+}
+
+/// feet_to_meters() converts a depth expressed in feet of sea water to
+/// meters of sea water
+pub fn feet_to_meters(feet: f64) -> f64 {
+  feet * METERS_PER_FOOT
+}
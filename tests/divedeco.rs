@@ -0,0 +1,103 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn divedeco(args: &[&str]) -> Output {
+  Command::new(env!("CARGO_BIN_EXE_divedeco"))
+    .args(args)
+    .output()
+    .expect("failed to run divedeco")
+}
+
+fn stdout(output: &Output) -> String {
+  String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn ndl_lookup() {
+  let output = divedeco(&["ndl", "--depth", "60"]);
+  assert_eq!(output.status.code(), Some(0));
+  assert_eq!(stdout(&output), "no-decompression limit at 60 fsw: 63 min\n");
+
+  let output = divedeco(&["ndl", "--depth", "18", "--units", "m", "--json"]);
+  assert_eq!(output.status.code(), Some(0));
+  let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+  assert_eq!(json["value"], 63);
+}
+
+#[test]
+fn repetitive_lookups() {
+  let output = divedeco(&["group", "--depth", "60", "--time", "40"]);
+  assert_eq!(stdout(&output), "repetitive group after 60 fsw for 40 min: H\n");
+
+  let output = divedeco(&["repet", "--depth", "60", "--time", "40", "--interval", "1:30", "--next-depth", "50"]);
+  assert_eq!(output.status.code(), Some(0));
+  assert_eq!(stdout(&output), "repetitive group after a 90 min surface interval: G\n");
+
+  let output = divedeco(&["rnt", "--depth", "60", "--time", "40", "--interval", "90", "--next-depth", "50", "--json"]);
+  let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+  assert_eq!(json["value"], 49);
+}
+
+#[test]
+fn deco_schedule() {
+  let output = divedeco(&["deco", "--depth", "100", "--time", "50"]);
+  assert_eq!(output.status.code(), Some(0));
+  let text = stdout(&output);
+  assert!(text.contains("stop 20 fsw: 47 min"));
+  assert!(text.contains("SurDO2 is recommended"));
+
+  let output = divedeco(&["deco", "--depth", "60", "--time", "30"]);
+  assert_eq!(stdout(&output), "no decompression required for 60 fsw / 30 min, repetitive group F\n");
+
+  let output = divedeco(&["deco", "--depth", "60", "--time", "30", "--json"]);
+  assert_eq!(output.status.code(), Some(0));
+  let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+  assert!(json["value"].is_null());
+  assert_eq!(json["group"], "F");
+  assert_eq!(json["advisories"][0]["code"], "no_decompression_required");
+}
+
+#[test]
+fn exit_codes() {
+  assert_eq!(divedeco(&["ndl", "--depth", "200"]).status.code(), Some(3));
+  assert_eq!(divedeco(&["deco", "--depth", "100", "--time", "500"]).status.code(), Some(3));
+
+  let output = divedeco(&["group", "--depth", "100", "--time", "500"]);
+  assert_eq!(output.status.code(), Some(3));
+  assert!(stdout(&output).starts_with("repetitive group after 100 fsw for 500 min: outside tables\n"));
+  assert_eq!(divedeco(&[]).status.code(), Some(2));
+  assert_eq!(divedeco(&["frob"]).status.code(), Some(2));
+  assert_eq!(divedeco(&["ndl"]).status.code(), Some(2));
+  assert_eq!(divedeco(&["ndl", "--depth", "deep"]).status.code(), Some(2));
+  assert_eq!(divedeco(&["ndl", "--depth", "60", "--units", "cubits"]).status.code(), Some(2));
+  assert_eq!(divedeco(&["plan", "/nonexistent/plan.txt"]).status.code(), Some(1));
+  assert_eq!(divedeco(&["--help"]).status.code(), Some(0));
+}
+
+#[test]
+fn plan_from_stdin() {
+  let mut child = Command::new(env!("CARGO_BIN_EXE_divedeco"))
+    .args(["plan", "-"])
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .unwrap();
+  child.stdin.take().unwrap().write_all(b"dive 60fsw 40min; surface 1h30; dive 50fsw 30min\n").unwrap();
+  let output = child.wait_with_output().unwrap();
+
+  assert_eq!(output.status.code(), Some(0));
+  assert_eq!(stdout(&output), "dive 1: 60 fsw / 40 min, no decompression, ends in group H\n\
+    dive 2: 50 fsw / 30 min, starts in group G with 49 min residual nitrogen, no decompression, ends in group K\n");
+}
+
+#[test]
+fn tables_listing() {
+  let output = divedeco(&["tables", "treatment"]);
+  assert_eq!(output.status.code(), Some(0));
+  assert!(stdout(&output).lines().any(|line| line.starts_with("TT6: ") && line.ends_with("(285 min)")));
+
+  let output = divedeco(&["tables", "nodeco", "--json"]);
+  let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+  assert_eq!(json["table_code"], "USN-AIR-NDL/GL");
+}